@@ -1,11 +1,12 @@
 use std::error::Error;
+use std::pin::pin;
 use std::time::Duration;
 
+use futures_util::TryStreamExt;
 use mcpi::connection::Tile;
 use mcpi::entity::Entity;
 use mcpi::{pos_to_tile, World};
-use nalgebra::{distance, Vector3};
-use tokio::time::sleep;
+use nalgebra::Vector3;
 
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
@@ -22,30 +23,27 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
     world.post("mcpi autobridge example").await?;
 
     let player = world.me();
-    let mut last_player_pos = player.get_position().await?;
-    loop {
-        sleep(Duration::from_millis(10)).await;
-
-        let player_pos = player.get_position().await?;
-
-        if distance(&player_pos, &last_player_pos) > 0.2 {
-            // The player is walking. Let's put a block under where they are about to step.
-
-            // First figure out where the player is walking to by continuing their
-            // trajectory until they hit a new tile.
-            let player_velocity = player_pos - last_player_pos;
+    // Only produce samples when the player has moved a noticeable distance.
+    let mut movement = pin!(player.movement_with_threshold(Duration::from_millis(10), 0.2));
+    while let Some(sample) = movement.try_next().await? {
+        if sample.velocity.norm() == 0.0 {
+            continue;
+        }
 
-            let player_tile = pos_to_tile(&player_pos);
-            let mut predicted_next_tile = player_pos;
-            while player_tile == pos_to_tile(&predicted_next_tile) {
-                predicted_next_tile += player_velocity;
-            }
+        // The player is walking. Let's put a block under where they are about to step.
 
-            // Now place a block under the predicted next tile.
-            let block_pos = pos_to_tile(&predicted_next_tile) - Vector3::new(0, 1, 0);
-            world.set_tile(block_pos, BRIDGE_BLOCK).await?;
+        // First figure out where the player is walking to by continuing their
+        // trajectory until they hit a new tile.
+        let step = sample.velocity.normalize() * 0.1;
+        let mut predicted_next_tile = sample.position;
+        while sample.tile == pos_to_tile(&predicted_next_tile) {
+            predicted_next_tile += step;
         }
 
-        last_player_pos = player_pos;
+        // Now place a block under the predicted next tile.
+        let block_pos = pos_to_tile(&predicted_next_tile) - Vector3::new(0, 1, 0);
+        world.set_tile(block_pos, BRIDGE_BLOCK).await?;
     }
+
+    Ok(())
 }
@@ -2,7 +2,7 @@ use std::pin::pin;
 use std::time::Duration;
 
 use futures_util::TryStreamExt;
-use mcpi::World;
+use mcpi::{ResolvedBlockHit, World};
 
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -14,15 +14,14 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let world = World::connect(addr).await?;
 
-    let mut block_stream = pin!(world.block_hits(Duration::from_millis(5)));
-    while let Some(hit) = block_stream.try_next().await? {
-        let block = world.get_block(hit.location).await?;
+    let mut block_stream = pin!(world.block_hits_with_blocks(Duration::from_millis(5)));
+    while let Some(ResolvedBlockHit { hit, block, player }) = block_stream.try_next().await? {
         println!(
             "{} block hit at {} (face: {:?}, player #{})",
             block.tile.display(),
             hit.location,
             hit.face,
-            hit.player_id
+            player.id()
         );
     }
 
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use futures_util::TryStreamExt;
 use mcpi::connection::Tile;
-use mcpi::World;
+use mcpi::{ResolvedBlockHit, World};
 use nalgebra::Vector3;
 use tokio::time::sleep;
 
@@ -27,14 +27,12 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
         .await?;
 
     let poll_frequency = Duration::from_millis(100);
-    let mut hits = pin!(world.block_hits(poll_frequency));
+    let mut hits = pin!(world.block_hits_with_blocks(poll_frequency));
 
-    while let Some(hit) = hits.try_next().await? {
+    while let Some(ResolvedBlockHit { hit, block, .. }) = hits.try_next().await? {
         // Start a background task so that multiple bombs can be ignited at once.
         let mut world = world.clone();
         tokio::spawn(async move {
-            let block = world.get_block(hit.location).await.unwrap();
-
             // Blink the exploding block a few times.
             for _ in 0..BLINKS {
                 world
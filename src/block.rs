@@ -1,19 +1,87 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use snafu::{OptionExt, Snafu};
+use nalgebra::Vector3;
+use snafu::{ensure, OptionExt, Snafu};
 
+use crate::block::state::{BlockState, Facing, StairsMaterial};
 use crate::connection::{Tile, TileData};
 use crate::Result;
 
+pub(crate) mod name;
+pub mod nbt;
+pub mod properties;
+pub mod sign;
+pub mod state;
+pub mod transform;
+
 /// A block type and its associated data.
+///
+/// Blocks can be parsed from their numeric form (`35,14`, optionally followed
+/// by NBT data as in `54,2,{"Items":[]}`) or from a
+/// human-readable name, such as `red wool`, `wool:red` or
+/// `oak_stairs[facing=east,half=top]`. They are displayed in a canonical form
+/// that can be parsed back, without their NBT data.
+///
+/// Names without data or a variant, with or without properties, start from a
+/// default state: ladders and furnaces face north, torches stand on the
+/// ground and doors are the closed bottom half facing west. Other tiles
+/// default to their zero data.
+///
+/// With the `serde` feature, formats that are not human-readable store NBT
+/// data as a JSON string.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct Block {
     pub tile: Tile,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub data: TileData,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_nbt")
+    )]
     pub nbt: Option<serde_json::Value>,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Block {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        // Only human-readable formats can skip fields or hold arbitrary JSON.
+        if !serializer.is_human_readable() {
+            let mut block = serializer.serialize_struct("Block", 3)?;
+            block.serialize_field("tile", &self.tile)?;
+            block.serialize_field("data", &self.data)?;
+            block.serialize_field("nbt", &self.json_nbt())?;
+            return block.end();
+        }
+        let mut block =
+            serializer.serialize_struct("Block", 2 + usize::from(self.nbt.is_some()))?;
+        block.serialize_field("tile", &self.tile)?;
+        block.serialize_field("data", &self.data)?;
+        if let Some(nbt) = &self.nbt {
+            block.serialize_field("nbt", nbt)?;
+        }
+        block.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_nbt<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<serde_json::Value>, D::Error> {
+    use serde::de::Error;
+    use serde::Deserialize;
+
+    if deserializer.is_human_readable() {
+        return Option::deserialize(deserializer);
+    }
+    Option::<String>::deserialize(deserializer)?
+        .map(|json| nbt::parse(&json).map_err(D::Error::custom))
+        .transpose()
+}
+
 impl Block {
     pub const fn new(tile: Tile, data: TileData) -> Self {
         Self {
@@ -44,19 +112,67 @@ pub enum ParseBlockError {
     ParseInt {
         source: ParseIntError,
     },
+    #[snafu(display("{source}"), context(false))]
+    InvalidNbt {
+        source: nbt::InvalidNbtError,
+    },
+    #[snafu(display("Unknown block `{name}`{}", did_you_mean(suggestion)))]
+    UnknownTile {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[snafu(display(
+        "Unknown variant `{name}` of {}{}",
+        tile.display(),
+        did_you_mean(suggestion)
+    ))]
+    UnknownData {
+        tile: Tile,
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[snafu(display("{} has no property `{property}`", tile.display()))]
+    UnknownProperty {
+        tile: Tile,
+        property: String,
+    },
+    #[snafu(display("Invalid value `{value}` for block property `{property}`"))]
+    InvalidPropertyValue {
+        property: String,
+        value: String,
+    },
+    #[snafu(display("Invalid block properties `{properties}`"))]
+    InvalidProperties {
+        properties: String,
+    },
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|suggestion| format!("; did you mean `{suggestion}`?"))
+        .unwrap_or_default()
 }
 
 impl FromStr for Block {
     type Err = ParseBlockError;
 
     fn from_str(s: &str) -> Result<Self, ParseBlockError> {
-        let (tile, data) = s.split_once(',').context(NotEnoughPartsSnafu)?;
-
-        Ok(Self {
-            tile: tile.parse()?,
-            data: data.parse()?,
-            nbt: None,
-        })
+        ensure!(!s.trim().is_empty(), NotEnoughPartsSnafu);
+        // Only numeric blocks such as `35,14` have a comma after the tile, as
+        // commas also separate properties in `stairs[facing=east,half=top]`.
+        let Some((tile, rest)) = s
+            .split_once(',')
+            .filter(|(tile, _)| !tile.is_empty() && tile.bytes().all(|b| b.is_ascii_digit()))
+        else {
+            return name::parse(s);
+        };
+        ensure!(!rest.trim().is_empty(), NotEnoughPartsSnafu);
+        let block = match rest.split_once(',') {
+            Some((data, nbt)) => Self::new(tile.parse()?, data.parse()?).with_nbt(nbt::parse(nbt)?),
+            None => Self::new(tile.parse()?, rest.parse()?),
+        };
+        Ok(block)
     }
 }
 
@@ -76,6 +192,7 @@ pub struct InvalidBlockFaceError {
 /// Represents a face of a block.
 #[repr(i16)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockFace {
     /// The side of the block facing towards Y = -∞ (i.e. the bottom of the
     /// block).
@@ -92,6 +209,125 @@ pub enum BlockFace {
     PositiveX,
 }
 
+/// Failed to parse a [`BlockFace`] from a string.
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid block face `{value}`"))]
+pub struct ParseBlockFaceError {
+    value: String,
+}
+
+impl BlockFace {
+    /// Returns every face, ordered by ID.
+    pub const fn all() -> [Self; 6] {
+        [
+            Self::NegativeY,
+            Self::PositiveY,
+            Self::NegativeZ,
+            Self::PositiveZ,
+            Self::NegativeX,
+            Self::PositiveX,
+        ]
+    }
+
+    /// Returns the face on the other side of the block.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::NegativeY => Self::PositiveY,
+            Self::PositiveY => Self::NegativeY,
+            Self::NegativeZ => Self::PositiveZ,
+            Self::PositiveZ => Self::NegativeZ,
+            Self::NegativeX => Self::PositiveX,
+            Self::PositiveX => Self::NegativeX,
+        }
+    }
+
+    /// Returns the unit vector pointing out of the face.
+    pub fn normal(self) -> Vector3<i16> {
+        match self {
+            Self::NegativeY => -Vector3::y(),
+            Self::PositiveY => Vector3::y(),
+            Self::NegativeZ => -Vector3::z(),
+            Self::PositiveZ => Vector3::z(),
+            Self::NegativeX => -Vector3::x(),
+            Self::PositiveX => Vector3::x(),
+        }
+    }
+
+    /// Returns the unit vector pointing out of the face, as a direction.
+    pub fn direction(self) -> Vector3<f64> {
+        self.normal().cast()
+    }
+
+    /// Returns the face that points the most towards the given direction, or
+    /// [`None`] if the direction is zero.
+    pub fn from_direction(direction: Vector3<f64>) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .map(|face| (face, face.direction().dot(&direction)))
+            .filter(|(_, alignment)| *alignment > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(face, _)| face)
+    }
+
+    /// Returns the side face that points the most towards an entity's yaw, in
+    /// degrees, where 0 faces +Z and 90 faces -X.
+    pub fn from_yaw(yaw: f64) -> Self {
+        match (yaw / 90.0).round().rem_euclid(4.0) as u8 {
+            1 => Self::NegativeX,
+            2 => Self::NegativeZ,
+            3 => Self::PositiveX,
+            _ => Self::PositiveZ,
+        }
+    }
+
+    /// Returns the yaw, in degrees, of an entity looking out of the face, or
+    /// [`None`] for the top and bottom faces. See [`Self::from_yaw`].
+    pub const fn yaw(self) -> Option<f64> {
+        match self {
+            Self::PositiveZ => Some(0.0),
+            Self::NegativeX => Some(90.0),
+            Self::NegativeZ => Some(180.0),
+            Self::PositiveX => Some(270.0),
+            Self::NegativeY | Self::PositiveY => None,
+        }
+    }
+
+    /// Returns the data that makes a block of the given tile face out of this
+    /// face, for stairs, ladders, wall signs and chests.
+    ///
+    /// Returns [`None`] for other tiles and for the top and bottom faces.
+    pub fn facing_data(self, tile: Tile) -> Option<TileData> {
+        let facing = Facing::try_from(self).ok()?;
+        let state = match tile {
+            Tile::LADDER => BlockState::Ladder { facing },
+            Tile::WALL_SIGN => BlockState::WallSign { facing },
+            Tile::CHEST => BlockState::Chest {
+                facing: Some(facing),
+            },
+            tile => BlockState::Stairs {
+                material: StairsMaterial::from_tile(tile)?,
+                facing,
+                upside_down: false,
+            },
+        };
+        Some(state.data())
+    }
+
+    /// Returns the direction that a block faces, for stairs, ladders, wall
+    /// signs and chests. See [`Self::facing_data`].
+    pub fn from_facing_data(tile: Tile, data: TileData) -> Option<Self> {
+        match Block::new(tile, data).state() {
+            BlockState::Stairs { facing, .. }
+            | BlockState::Ladder { facing }
+            | BlockState::WallSign { facing }
+            | BlockState::Chest {
+                facing: Some(facing),
+            } => Some(facing.into()),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<u8> for BlockFace {
     type Error = InvalidBlockFaceError;
 
@@ -107,3 +343,139 @@ impl TryFrom<u8> for BlockFace {
         })
     }
 }
+
+impl TryFrom<i32> for BlockFace {
+    type Error = InvalidBlockFaceError;
+
+    fn try_from(id: i32) -> Result<Self, InvalidBlockFaceError> {
+        u8::try_from(id)
+            .ok()
+            .and_then(|id| Self::try_from(id).ok())
+            .context(InvalidBlockFaceSnafu { id })
+    }
+}
+
+impl FromStr for BlockFace {
+    type Err = ParseBlockFaceError;
+
+    /// Parses a face from its ID, its axis (such as `+x`), a direction (such
+    /// as `east` or `up`), or its variant name.
+    fn from_str(s: &str) -> Result<Self, ParseBlockFaceError> {
+        let value = s.trim().to_ascii_lowercase().replace(['_', ' '], "");
+        Ok(match value.as_str() {
+            "0" | "-y" | "down" | "bottom" | "negativey" => Self::NegativeY,
+            "1" | "+y" | "up" | "top" | "positivey" => Self::PositiveY,
+            "2" | "-z" | "north" | "negativez" => Self::NegativeZ,
+            "3" | "+z" | "south" | "positivez" => Self::PositiveZ,
+            "4" | "-x" | "west" | "negativex" => Self::NegativeX,
+            "5" | "+x" | "east" | "positivex" => Self::PositiveX,
+            _ => return ParseBlockFaceSnafu { value: s }.fail(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_face_geometry() {
+        for face in BlockFace::all() {
+            assert_eq!(face.opposite().normal(), -face.normal());
+            assert_eq!(BlockFace::from_direction(face.direction()), Some(face));
+            if let Some(yaw) = face.yaw() {
+                assert_eq!(BlockFace::from_yaw(yaw), face);
+            }
+            assert_eq!(BlockFace::try_from(face as i32).unwrap(), face);
+        }
+        assert_eq!(
+            BlockFace::from_direction(Vector3::new(0.2, -0.9, 0.1)),
+            Some(BlockFace::NegativeY)
+        );
+        assert_eq!(BlockFace::from_direction(Vector3::zeros()), None);
+        assert_eq!(BlockFace::from_yaw(-100.0), BlockFace::PositiveX);
+        assert!(BlockFace::try_from(6).is_err());
+        assert!(BlockFace::try_from(-1).is_err());
+    }
+
+    #[test]
+    fn block_face_parse() {
+        assert_eq!("east".parse::<BlockFace>().unwrap(), BlockFace::PositiveX);
+        assert_eq!("-Z".parse::<BlockFace>().unwrap(), BlockFace::NegativeZ);
+        assert_eq!("Up".parse::<BlockFace>().unwrap(), BlockFace::PositiveY);
+        assert_eq!("0".parse::<BlockFace>().unwrap(), BlockFace::NegativeY);
+        assert_eq!(
+            "NegativeX".parse::<BlockFace>().unwrap(),
+            BlockFace::NegativeX
+        );
+        assert!("sideways".parse::<BlockFace>().is_err());
+    }
+
+    #[test]
+    fn block_face_facing_data() {
+        assert_eq!(
+            BlockFace::PositiveX.facing_data(Tile::WOODEN_STAIRS),
+            Some(TileData::STAIRS_X_POSITIVE)
+        );
+        assert_eq!(
+            BlockFace::NegativeZ.facing_data(Tile::CHEST),
+            Some(TileData::CHEST_Z_NEGATIVE)
+        );
+        assert_eq!(
+            BlockFace::NegativeX.facing_data(Tile::WALL_SIGN),
+            Some(TileData::WALL_SIGN_X_NEGATIVE)
+        );
+        assert_eq!(BlockFace::PositiveY.facing_data(Tile::LADDER), None);
+        assert_eq!(BlockFace::PositiveX.facing_data(Tile::STONE), None);
+        for face in BlockFace::all().into_iter().skip(2) {
+            for tile in [
+                Tile::QUARTZ_STAIRS,
+                Tile::LADDER,
+                Tile::WALL_SIGN,
+                Tile::CHEST,
+            ] {
+                let data = face.facing_data(tile).unwrap();
+                assert_eq!(BlockFace::from_facing_data(tile, data), Some(face));
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn block_serde() {
+        use serde_json::json;
+
+        let block = Block::new(Tile::WOOL, TileData::RED);
+        let value = serde_json::to_value(&block).unwrap();
+        assert_eq!(value, json!({ "tile": "wool", "data": 14 }));
+        assert_eq!(serde_json::from_value::<Block>(value).unwrap(), block);
+
+        let block = Block::from_tile(Tile(200)).with_nbt(json!({ "id": "Chest" }));
+        let value = serde_json::to_value(&block).unwrap();
+        assert_eq!(
+            value,
+            json!({ "tile": "200", "data": 0, "nbt": { "id": "Chest" } })
+        );
+        assert_eq!(serde_json::from_value::<Block>(value).unwrap(), block);
+
+        let block = serde_json::from_value::<Block>(json!({ "tile": "Diamond Block" }));
+        assert_eq!(block.unwrap(), Block::from_tile(Tile::DIAMOND_BLOCK));
+        let block = serde_json::from_value::<Block>(json!({ "tile": 1, "data": 2 }));
+        assert_eq!(block.unwrap(), Block::new(Tile::STONE, TileData(2)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn block_bincode_round_trips() {
+        let blocks = [
+            Block::new(Tile::WOOL, TileData::RED),
+            Block::from_tile(Tile(200)),
+            Block::from_tile(Tile::CHEST).with_nbt(serde_json::json!({ "Items": [] })),
+        ];
+        for block in blocks {
+            let bytes = bincode::serialize(&block).unwrap();
+            assert_eq!(bincode::deserialize::<Block>(&bytes).unwrap(), block);
+        }
+        assert_eq!(bincode::serialize(&Tile::WOOL).unwrap(), [35]);
+    }
+}
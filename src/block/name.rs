@@ -0,0 +1,572 @@
+//! Human-readable block names, such as `red wool` or
+//! `oak_stairs[facing=east,half=top]`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use snafu::OptionExt;
+
+use crate::block::state::{BlockState, Color, DoorHinge, Facing, SlabMaterial};
+use crate::block::{
+    Block, InvalidPropertiesSnafu, InvalidPropertyValueSnafu, ParseBlockError, UnknownDataSnafu,
+    UnknownPropertySnafu, UnknownTileSnafu,
+};
+use crate::connection::{Tile, TileData};
+
+/// Other names that tiles are commonly known by.
+const ALIASES: [(&str, Tile); 3] = [
+    ("oak_stairs", Tile::WOODEN_STAIRS),
+    ("oak_door", Tile::WOODEN_DOOR),
+    ("oak_planks", Tile::PLANKS),
+];
+
+/// Turns a name such as `Light Blue` or `LIGHT_BLUE` into `light_blue`.
+fn normalize(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// The canonical names of vanilla tiles, such as `diamond_block`.
+static TILE_NAMES: LazyLock<HashMap<Tile, String>> = LazyLock::new(|| {
+    (0..=u8::MAX)
+        .map(Tile)
+        .filter(|tile| tile.properties().is_some())
+        .map(|tile| (tile, normalize(&tile.display().to_string())))
+        .collect()
+});
+
+static TILES: LazyLock<HashMap<String, Tile>> = LazyLock::new(|| {
+    TILE_NAMES
+        .iter()
+        .map(|(&tile, name)| (name.clone(), tile))
+        .chain(ALIASES.map(|(name, tile)| (name.to_owned(), tile)))
+        .collect()
+});
+
+static VARIANTS: LazyLock<HashMap<Tile, Vec<(String, TileData)>>> = LazyLock::new(|| {
+    (0..=u8::MAX)
+        .map(Tile)
+        .map(|tile| (tile, named_variants(tile)))
+        .filter(|(_, variants)| !variants.is_empty())
+        .collect()
+});
+
+/// Returns the canonical name of a vanilla tile, such as `diamond_block`.
+pub(crate) fn tile_name(tile: Tile) -> Option<&'static str> {
+    TILE_NAMES.get(&tile).map(String::as_str)
+}
+
+/// Returns the names of a tile's variants, such as the colors of wool, along
+/// with their data.
+fn variants(tile: Tile) -> &'static [(String, TileData)] {
+    VARIANTS.get(&tile).map_or(&[], Vec::as_slice)
+}
+
+fn named_variants(tile: Tile) -> Vec<(String, TileData)> {
+    let named: &[(&str, TileData)] = match tile {
+        Tile::WOOL => {
+            return Color::ALL
+                .into_iter()
+                .map(|color| (normalize(color.name()), color.into()))
+                .collect();
+        }
+        Tile::SLAB | Tile::DOUBLE_SLAB => {
+            return SlabMaterial::ALL
+                .into_iter()
+                .zip(0..)
+                .map(|(material, id)| (normalize(material.name()), TileData(id)))
+                .collect();
+        }
+        Tile::LOG | Tile::LEAVES | Tile::SAPLING => &[
+            ("oak", TileData::OAK),
+            ("spruce", TileData::SPRUCE),
+            ("birch", TileData::BIRCH),
+        ],
+        Tile::LEAVES_CARRIED => &[
+            ("dark_oak", TileData::LEAVES_CARRIED_DARK_OAK),
+            ("dark_spruce", TileData::LEAVES_CARRIED_DARK_SPRUCE),
+            ("dark_birch", TileData::LEAVES_CARRIED_DARK_BIRCH),
+        ],
+        Tile::SANDSTONE => &[
+            ("normal", TileData::SANDSTONE_NORMAL),
+            ("chiselled", TileData::SANDSTONE_CHISELLED),
+        ],
+        Tile::BUSH => &[
+            ("dead", TileData::BUSH_DEAD),
+            ("grass", TileData::BUSH_GRASS),
+            ("fern", TileData::BUSH_FERN),
+        ],
+        Tile::TNT => &[
+            ("inactive", TileData::TNT_INACTIVE),
+            ("active", TileData::TNT_ACTIVE),
+        ],
+        Tile::QUARTZ => &[
+            ("normal", TileData::QUARTZ_NORMAL),
+            ("chiselled", TileData::QUARTZ_CHISELLED),
+            ("pillar", TileData::QUARTZ_PILLAR),
+        ],
+        Tile::FARMLAND => &[
+            ("dry", TileData::FARMLAND_DRY),
+            ("wet", TileData::FARMLAND_WET),
+        ],
+        Tile::NETHER_REACTOR_CORE => &[
+            ("normal", TileData::NETHER_REACTOR_CORE_NORMAL),
+            ("active", TileData::NETHER_REACTOR_CORE_ACTIVE),
+            ("burned", TileData::NETHER_REACTOR_CORE_BURNED),
+        ],
+        _ => &[],
+    };
+    named
+        .iter()
+        .map(|&(name, data)| (name.to_owned(), data))
+        .collect()
+}
+
+fn find_variant(tile: Tile, name: &str) -> Option<TileData> {
+    variants(tile)
+        .iter()
+        .find_map(|(variant, data)| (variant == name).then_some(*data))
+}
+
+/// Returns the number of single character edits needed to turn `a` into `b`,
+/// counting swapped adjacent characters as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Returns the candidate closest to `name`, if any is close enough to be a
+/// likely typo.
+fn suggest(name: &str, candidates: impl IntoIterator<Item = String>) -> Option<String> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, &candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (name.len().max(candidate.len()) / 3).max(1))
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Parses a tile name or ID, which may start with a variant as in `red_wool`.
+pub(crate) fn parse_tile(name: &str) -> Result<(Tile, Option<TileData>), ParseBlockError> {
+    if let Ok(id) = name.trim().parse() {
+        return Ok((Tile(id), None));
+    }
+    let name = normalize(name);
+    if let Some(&tile) = TILES.get(&name) {
+        return Ok((tile, None));
+    }
+    for (i, _) in name.match_indices('_') {
+        let (variant, tile) = (&name[..i], &name[i + 1..]);
+        if let Some(&tile) = TILES.get(tile) {
+            if let Some(data) = find_variant(tile, variant) {
+                return Ok((tile, Some(data)));
+            }
+        }
+    }
+
+    let candidates = TILES.iter().flat_map(|(tile_name, &tile)| {
+        let variants = variants(tile)
+            .iter()
+            .map(move |(variant, _)| format!("{variant}_{tile_name}"));
+        std::iter::once(tile_name.clone()).chain(variants)
+    });
+    UnknownTileSnafu {
+        suggestion: suggest(&name, candidates),
+        name,
+    }
+    .fail()
+}
+
+/// Parses a data value or the name of one of the tile's variants.
+fn parse_data(tile: Tile, name: &str) -> Result<TileData, ParseBlockError> {
+    if let Ok(data) = name.trim().parse() {
+        return Ok(data);
+    }
+    let name = normalize(name);
+    find_variant(tile, &name).with_context(|| UnknownDataSnafu {
+        tile,
+        suggestion: suggest(&name, variants(tile).iter().map(|(name, _)| name.clone())),
+        name: name.clone(),
+    })
+}
+
+fn parse_facing(value: &str) -> Option<Facing> {
+    Some(match value {
+        "east" | "+x" => Facing::PositiveX,
+        "west" | "-x" => Facing::NegativeX,
+        "south" | "+z" => Facing::PositiveZ,
+        "north" | "-z" => Facing::NegativeZ,
+        _ => return None,
+    })
+}
+
+/// Returns the state of a tile when no data or variant is given, as described
+/// on [`Block`]. The zero data of these tiles is either not a valid state or
+/// an open door.
+fn default_state(tile: Tile) -> BlockState {
+    match tile {
+        Tile::LADDER => BlockState::Ladder {
+            facing: Facing::NegativeZ,
+        },
+        Tile::FURNACE | Tile::LIT_FURNACE => BlockState::Furnace {
+            facing: Facing::NegativeZ,
+            lit: tile == Tile::LIT_FURNACE,
+        },
+        Tile::TORCH => BlockState::Torch { facing: None },
+        Tile::WOODEN_DOOR | Tile::IRON_DOOR => {
+            Block::new(tile, TileData::DOOR_CLOSED_BOTTOM_X_NEGATIVE).state()
+        }
+        tile => Block::from_tile(tile).state(),
+    }
+}
+
+/// Returns the data of [`default_state`].
+fn default_data(tile: Tile) -> TileData {
+    default_state(tile).data()
+}
+
+/// Returns whether the value names the top half of a block.
+fn parse_half(value: &str) -> Option<bool> {
+    match value {
+        "top" => Some(true),
+        "bottom" => Some(false),
+        _ => None,
+    }
+}
+
+fn set_property(state: &mut BlockState, key: &str, value: &str) -> Result<(), ParseBlockError> {
+    let invalid = || InvalidPropertyValueSnafu {
+        property: key,
+        value,
+    };
+    match (key, &mut *state) {
+        ("facing", BlockState::Chest { facing } | BlockState::Torch { facing }) => {
+            *facing = match value {
+                "none" => None,
+                value => Some(parse_facing(value).with_context(invalid)?),
+            };
+        }
+        (
+            "facing",
+            BlockState::Stairs { facing, .. }
+            | BlockState::Door { facing, .. }
+            | BlockState::Ladder { facing }
+            | BlockState::WallSign { facing }
+            | BlockState::Bed { facing, .. }
+            | BlockState::Trapdoor { facing, .. }
+            | BlockState::FenceGate { facing, .. }
+            | BlockState::Furnace { facing, .. },
+        ) => *facing = parse_facing(value).with_context(invalid)?,
+        (
+            "half",
+            BlockState::Stairs {
+                upside_down: top, ..
+            }
+            | BlockState::Slab { top, .. },
+        ) => *top = parse_half(value).with_context(invalid)?,
+        ("half", BlockState::Door { material, .. } | BlockState::DoorTop { material, .. }) => {
+            let material = *material;
+            match (parse_half(value).with_context(invalid)?, &*state) {
+                (true, BlockState::Door { .. }) => {
+                    *state = BlockState::DoorTop {
+                        material,
+                        hinge: DoorHinge::Left,
+                        powered: false,
+                    };
+                }
+                (false, BlockState::DoorTop { .. }) => {
+                    *state = default_state(material.tile());
+                }
+                _ => {}
+            }
+        }
+        (
+            "open",
+            BlockState::Door { open, .. }
+            | BlockState::Trapdoor { open, .. }
+            | BlockState::FenceGate { open, .. },
+        ) => {
+            *open = value.parse().ok().with_context(invalid)?;
+        }
+        ("hinge", BlockState::DoorTop { hinge, .. }) => {
+            *hinge = match value {
+                "left" => DoorHinge::Left,
+                "right" => DoorHinge::Right,
+                _ => return invalid().fail(),
+            };
+        }
+        ("powered", BlockState::DoorTop { powered, .. }) => {
+            *powered = value.parse().ok().with_context(invalid)?;
+        }
+        ("part", BlockState::Bed { head, .. }) => {
+            *head = match value {
+                "head" => true,
+                "foot" => false,
+                _ => return invalid().fail(),
+            };
+        }
+        ("lit", BlockState::Furnace { lit, .. }) => {
+            *lit = value.parse().ok().with_context(invalid)?;
+        }
+        ("color", BlockState::Wool(color)) => {
+            let value = normalize(value);
+            *color = Color::ALL
+                .into_iter()
+                .find(|color| normalize(color.name()) == value)
+                .with_context(invalid)?;
+        }
+        (_, state) => {
+            return UnknownPropertySnafu {
+                tile: state.tile(),
+                property: key,
+            }
+            .fail()
+        }
+    }
+    Ok(())
+}
+
+/// Parses a block from a name such as `red wool`, `wool:red`, `WOOL:14` or
+/// `oak_stairs[facing=east,half=top]`.
+pub(super) fn parse(s: &str) -> Result<Block, ParseBlockError> {
+    let s = s.trim();
+    let (name, properties) = match s.split_once('[') {
+        Some((name, properties)) => {
+            let properties = properties
+                .strip_suffix(']')
+                .context(InvalidPropertiesSnafu { properties })?;
+            (name, properties)
+        }
+        None => (s, ""),
+    };
+
+    let mut state = match name.split_once(':') {
+        Some((tile, data)) => {
+            let (tile, _) = parse_tile(tile)?;
+            Block::new(tile, parse_data(tile, data)?).state()
+        }
+        None => match parse_tile(name)? {
+            (tile, Some(variant)) => Block::new(tile, variant).state(),
+            (tile, None) => default_state(tile),
+        },
+    };
+
+    let mut properties = properties
+        .split(',')
+        .filter(|p| !p.trim().is_empty())
+        .map(|property| {
+            let (key, value) = property
+                .split_once('=')
+                .context(InvalidPropertiesSnafu { properties })?;
+            Ok((normalize(key), value.trim().to_ascii_lowercase()))
+        })
+        .collect::<Result<Vec<_>, ParseBlockError>>()?;
+    // The half of a door decides which properties it has, so it is set first.
+    properties.sort_by_key(|(key, _)| key != "half");
+    for (key, value) in properties {
+        set_property(&mut state, &key, &value)?;
+    }
+    Ok(state.into())
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match tile_name(self.tile) {
+            Some(name) => f.write_str(name)?,
+            None => write!(f, "{}", self.tile)?,
+        }
+        if self.data != default_data(self.tile) {
+            match variants(self.tile)
+                .iter()
+                .find(|(_, data)| *data == self.data)
+            {
+                Some((name, _)) => write!(f, ":{name}")?,
+                None => write!(f, ":{}", self.data)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Block {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn block_parse_names() {
+        let red_wool = Block::new(Tile::WOOL, TileData::RED);
+        assert_eq!(parse("35,14"), red_wool);
+        assert_eq!(parse("red wool"), red_wool);
+        assert_eq!(parse("Red Wool"), red_wool);
+        assert_eq!(parse("wool:red"), red_wool);
+        assert_eq!(parse("WOOL:14"), red_wool);
+        assert_eq!(parse("wool[color=red]"), red_wool);
+        assert_eq!(
+            parse("DIAMOND_BLOCK"),
+            Block::from_tile(Tile::DIAMOND_BLOCK)
+        );
+        assert_eq!(parse("87"), Block::from_tile(Tile::NETHERRACK));
+        assert_eq!(
+            parse("stone brick slab[half=top]"),
+            Block::new(Tile::SLAB, TileData::SLAB_STONE_BRICKS_TOP)
+        );
+        assert_eq!(
+            parse("oak_stairs[facing=east,half=top]"),
+            Block::new(Tile::WOODEN_STAIRS, TileData::STAIRS_X_POSITIVE_UPSIDE_DOWN)
+        );
+        assert_eq!(
+            parse("ladder[facing=west]"),
+            BlockState::Ladder {
+                facing: Facing::NegativeX
+            }
+            .into()
+        );
+        assert_eq!(
+            parse("wooden_door[facing=+z,open=false]"),
+            Block::new(Tile::WOODEN_DOOR, TileData::DOOR_CLOSED_BOTTOM_Z_POSITIVE)
+        );
+        assert_eq!(
+            parse("torch[facing=north]"),
+            BlockState::Torch {
+                facing: Some(Facing::NegativeZ)
+            }
+            .into()
+        );
+        assert_eq!(
+            parse("iron_door[half=top,hinge=right]"),
+            Block::new(Tile::IRON_DOOR, TileData(9))
+        );
+    }
+
+    #[test]
+    fn block_parse_property_order() {
+        for (a, b) in [
+            ("facing=east", "half=bottom"),
+            ("hinge=right", "half=top"),
+            ("facing=south", "open=true"),
+        ] {
+            let block = parse(&format!("oak_door[{a},{b}]"));
+            assert_eq!(parse(&format!("oak_door[{b},{a}]")), block, "{a},{b}");
+        }
+        assert_eq!(
+            parse("oak_door[facing=east,half=bottom]"),
+            Block::new(Tile::WOODEN_DOOR, TileData::DOOR_CLOSED_BOTTOM_X_POSITIVE)
+        );
+        assert_eq!(
+            parse("iron_door[hinge=right,half=top]"),
+            Block::new(Tile::IRON_DOOR, TileData(9))
+        );
+    }
+
+    #[test]
+    fn block_parse_default_states() {
+        assert_eq!(
+            parse("oak_door"),
+            Block::new(Tile::WOODEN_DOOR, TileData::DOOR_CLOSED_BOTTOM_X_NEGATIVE)
+        );
+        assert_eq!(parse("oak_door[half=bottom]"), parse("oak_door"));
+        assert_eq!(
+            parse("oak_door[facing=east]"),
+            Block::new(Tile::WOODEN_DOOR, TileData::DOOR_CLOSED_BOTTOM_X_POSITIVE)
+        );
+        for name in ["ladder", "furnace", "torch", "stone", "oak_door"] {
+            assert_eq!(parse(&format!("{name}[]")), parse(name), "{name}");
+        }
+        assert_eq!(parse("ladder"), Block::new(Tile::LADDER, TileData(2)));
+        assert_eq!(parse("ladder:0"), Block::from_tile(Tile::LADDER));
+    }
+
+    #[test]
+    fn block_parse_suggestions() {
+        let error = "diamond_blok".parse::<Block>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown block `diamond_blok`; did you mean `diamond_block`?"
+        );
+        let error = "wool:rde".parse::<Block>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown variant `rde` of Wool; did you mean `red`?"
+        );
+        assert!(matches!(
+            "yellow wol".parse::<Block>(),
+            Err(ParseBlockError::UnknownTile { suggestion: Some(s), .. }) if s == "yellow_wool"
+        ));
+        assert!(matches!(
+            "xyzzy".parse::<Block>(),
+            Err(ParseBlockError::UnknownTile {
+                suggestion: None,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "".parse::<Block>(),
+            Err(ParseBlockError::NotEnoughParts)
+        ));
+        assert!(matches!(
+            "35,".parse::<Block>(),
+            Err(ParseBlockError::NotEnoughParts)
+        ));
+        assert!(matches!(
+            "red wool, please".parse::<Block>(),
+            Err(ParseBlockError::UnknownTile { .. })
+        ));
+        assert_eq!(
+            "oak_stairs[facing=east"
+                .parse::<Block>()
+                .unwrap_err()
+                .to_string(),
+            "Invalid block properties `facing=east`"
+        );
+        assert!(matches!(
+            "stone[facing=east]".parse::<Block>(),
+            Err(ParseBlockError::UnknownProperty { .. })
+        ));
+        assert!(matches!(
+            "oak_stairs[facing=up]".parse::<Block>(),
+            Err(ParseBlockError::InvalidPropertyValue { .. })
+        ));
+    }
+
+    #[test]
+    fn block_display_round_trips() {
+        assert_eq!(
+            Block::new(Tile::WOOL, TileData::RED).to_string(),
+            "wool:red"
+        );
+        assert_eq!(Block::from_tile(Tile::TNT).to_string(), "tnt");
+        assert_eq!(Block::new(Tile(200), TileData(3)).to_string(), "200:3");
+        for tile in (0..=u8::MAX).map(Tile) {
+            for data in (0..16).map(TileData) {
+                let block = Block::new(tile, data);
+                assert_eq!(parse(&block.to_string()), block, "{block}");
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Builders for common NBT payloads, which Raspberry Jam servers accept as
+//! JSON when setting blocks.
+
+use serde_json::{json, Map, Value};
+use snafu::{ensure, ResultExt, Snafu};
+
+use crate::block::sign::MAX_SIGN_LINES;
+use crate::block::state::Color;
+use crate::block::Block;
+
+/// An error that occurs when NBT data cannot be sent to the server.
+#[derive(Debug, Snafu)]
+pub enum InvalidNbtError {
+    #[snafu(display("Invalid NBT JSON: {source}"))]
+    Json { source: serde_json::Error },
+    #[snafu(display("NBT data must be a JSON object"))]
+    NotAnObject,
+}
+
+/// Parses NBT data from a JSON object.
+pub fn parse(json: &str) -> Result<Value, InvalidNbtError> {
+    let nbt = serde_json::from_str(json).context(JsonSnafu)?;
+    validate(&nbt)?;
+    Ok(nbt)
+}
+
+/// Checks that NBT data can be sent to the server.
+///
+/// The data must be a JSON object. Serialized JSON never contains line
+/// breaks, so it always fits on a single line of the protocol.
+pub fn validate(nbt: &Value) -> Result<(), InvalidNbtError> {
+    ensure!(nbt.is_object(), NotAnObjectSnafu);
+    Ok(())
+}
+
+impl Block {
+    /// Sets the block's NBT data from a JSON object, checking that it can be
+    /// sent to the server.
+    pub fn with_json_nbt(self, json: &str) -> Result<Self, InvalidNbtError> {
+        Ok(self.with_nbt(parse(json)?))
+    }
+}
+
+/// A stack of items, as stored in a chest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The item's ID, such as `minecraft:diamond`.
+    pub id: String,
+    pub count: u8,
+    /// The item's damage, or its variant for items such as dyes.
+    pub damage: i16,
+}
+
+impl Item {
+    /// Creates a single item with the given ID.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            count: 1,
+            damage: 0,
+        }
+    }
+
+    pub const fn with_count(mut self, count: u8) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub const fn with_damage(mut self, damage: i16) -> Self {
+        self.damage = damage;
+        self
+    }
+}
+
+/// The contents of a chest.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ChestNbt {
+    items: Vec<(u8, Item)>,
+}
+
+impl ChestNbt {
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Puts an item in the given slot, from 0 to 26.
+    pub fn with_item(mut self, slot: u8, item: Item) -> Self {
+        self.items.push((slot, item));
+        self
+    }
+}
+
+impl From<ChestNbt> for Value {
+    fn from(chest: ChestNbt) -> Self {
+        let items = chest
+            .items
+            .into_iter()
+            .map(|(slot, item)| {
+                json!({
+                    "Slot": slot,
+                    "id": item.id,
+                    "Count": item.count,
+                    "Damage": item.damage,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "Items": items })
+    }
+}
+
+/// The text on a sign.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SignNbt {
+    lines: Vec<String>,
+}
+
+impl SignNbt {
+    /// Creates sign text from up to four lines. Extra lines are ignored.
+    pub fn new<S: Into<String>>(lines: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            lines: lines
+                .into_iter()
+                .take(MAX_SIGN_LINES)
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<SignNbt> for Value {
+    fn from(sign: SignNbt) -> Self {
+        let mut nbt = Map::new();
+        for i in 0..MAX_SIGN_LINES {
+            let line = sign.lines.get(i).map(String::as_str).unwrap_or_default();
+            // Each line is stored as a serialized text component.
+            let text = json!({ "text": line }).to_string();
+            nbt.insert(format!("Text{}", i + 1), text.into());
+        }
+        nbt.into()
+    }
+}
+
+/// The configuration of a mob spawner.
+///
+/// Settings that are not set keep the game's defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpawnerNbt {
+    /// The ID of the entity to spawn, such as `Zombie`.
+    pub entity: String,
+    /// Ticks until the next spawn.
+    pub delay: Option<i16>,
+    pub min_spawn_delay: Option<i16>,
+    pub max_spawn_delay: Option<i16>,
+    /// The number of entities to spawn at once.
+    pub spawn_count: Option<i16>,
+    /// The distance from the spawner that entities can appear in.
+    pub spawn_range: Option<i16>,
+    /// The distance a player must be within for entities to spawn.
+    pub required_player_range: Option<i16>,
+}
+
+impl SpawnerNbt {
+    pub fn new(entity: impl Into<String>) -> Self {
+        Self {
+            entity: entity.into(),
+            delay: None,
+            min_spawn_delay: None,
+            max_spawn_delay: None,
+            spawn_count: None,
+            spawn_range: None,
+            required_player_range: None,
+        }
+    }
+
+    pub const fn with_delay(mut self, ticks: i16) -> Self {
+        self.delay = Some(ticks);
+        self
+    }
+
+    pub const fn with_spawn_delay(mut self, min_ticks: i16, max_ticks: i16) -> Self {
+        self.min_spawn_delay = Some(min_ticks);
+        self.max_spawn_delay = Some(max_ticks);
+        self
+    }
+
+    pub const fn with_spawn_count(mut self, count: i16) -> Self {
+        self.spawn_count = Some(count);
+        self
+    }
+
+    pub const fn with_spawn_range(mut self, range: i16) -> Self {
+        self.spawn_range = Some(range);
+        self
+    }
+
+    pub const fn with_required_player_range(mut self, range: i16) -> Self {
+        self.required_player_range = Some(range);
+        self
+    }
+}
+
+impl From<SpawnerNbt> for Value {
+    fn from(spawner: SpawnerNbt) -> Self {
+        // Older versions read `EntityId`, newer ones read `SpawnData`.
+        let mut nbt = Map::new();
+        nbt.insert("EntityId".into(), spawner.entity.clone().into());
+        nbt.insert("SpawnData".into(), json!({ "id": spawner.entity }));
+        let settings = [
+            ("Delay", spawner.delay),
+            ("MinSpawnDelay", spawner.min_spawn_delay),
+            ("MaxSpawnDelay", spawner.max_spawn_delay),
+            ("SpawnCount", spawner.spawn_count),
+            ("SpawnRange", spawner.spawn_range),
+            ("RequiredPlayerRange", spawner.required_player_range),
+        ];
+        for (key, value) in settings {
+            if let Some(value) = value {
+                nbt.insert(key.into(), value.into());
+            }
+        }
+        nbt.into()
+    }
+}
+
+/// The base color and patterns of a banner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BannerNbt {
+    pub base: Color,
+    /// Patterns from bottom to top, by their short code (such as `cre` for a
+    /// creeper face or `bri` for a bordure) and color.
+    pub patterns: Vec<(String, Color)>,
+}
+
+impl BannerNbt {
+    pub const fn new(base: Color) -> Self {
+        Self {
+            base,
+            patterns: Vec::new(),
+        }
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Into<String>, color: Color) -> Self {
+        self.patterns.push((pattern.into(), color));
+        self
+    }
+}
+
+/// Returns the dye value of a color, which banners use instead of wool colors.
+const fn dye(color: Color) -> u8 {
+    15 - color.id()
+}
+
+impl From<BannerNbt> for Value {
+    fn from(banner: BannerNbt) -> Self {
+        let patterns = banner
+            .patterns
+            .into_iter()
+            .map(|(pattern, color)| json!({ "Pattern": pattern, "Color": dye(color) }))
+            .collect::<Vec<_>>();
+        json!({ "Base": dye(banner.base), "Patterns": patterns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::{Tile, TileData};
+
+    #[test]
+    fn nbt_builders() {
+        let chest = ChestNbt::new().with_item(3, Item::new("minecraft:diamond").with_count(5));
+        assert_eq!(
+            Value::from(chest),
+            json!({ "Items": [{ "Slot": 3, "id": "minecraft:diamond", "Count": 5, "Damage": 0 }] })
+        );
+
+        let sign = Value::from(SignNbt::new(["Hi, there"]));
+        assert_eq!(sign["Text1"], r#"{"text":"Hi, there"}"#);
+        assert_eq!(sign["Text4"], r#"{"text":""}"#);
+
+        let spawner = Value::from(SpawnerNbt::new("Zombie").with_delay(20));
+        assert_eq!(
+            spawner,
+            json!({ "EntityId": "Zombie", "SpawnData": { "id": "Zombie" }, "Delay": 20 })
+        );
+
+        let banner = BannerNbt::new(Color::White).with_pattern("cre", Color::Black);
+        assert_eq!(
+            Value::from(banner),
+            json!({ "Base": 15, "Patterns": [{ "Pattern": "cre", "Color": 0 }] })
+        );
+    }
+
+    #[test]
+    fn nbt_validation() {
+        let block = Block::from_tile(Tile::CHEST)
+            .with_json_nbt(r#"{ "Items": [] }"#)
+            .unwrap();
+        assert_eq!(block.json_nbt().unwrap(), r#"{"Items":[]}"#);
+        assert!(matches!(
+            Block::from_tile(Tile::CHEST).with_json_nbt("[1, 2]"),
+            Err(InvalidNbtError::NotAnObject)
+        ));
+        assert!(matches!(
+            Block::from_tile(Tile::CHEST).with_json_nbt("{"),
+            Err(InvalidNbtError::Json { .. })
+        ));
+    }
+
+    #[test]
+    fn block_parse_nbt() {
+        let block = r#"54,2,{"Items":[{"Slot":0,"id":"minecraft:stone"}]}"#
+            .parse::<Block>()
+            .unwrap();
+        assert_eq!(
+            block,
+            Block::new(Tile::CHEST, TileData::CHEST_Z_NEGATIVE)
+                .with_nbt(json!({ "Items": [{ "Slot": 0, "id": "minecraft:stone" }] }))
+        );
+    }
+}
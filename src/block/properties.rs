@@ -0,0 +1,263 @@
+//! Static information about how each vanilla tile behaves.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::LazyLock;
+
+use crate::connection::{Tile, TileData};
+
+/// Describes how a tile behaves in the game world.
+///
+/// Properties of vanilla tiles can be looked up using [`Tile::properties`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockProperties {
+    /// Whether entities collide with the tile and can stand on top of it.
+    pub solid: bool,
+    /// Whether light and vision pass through the tile.
+    pub transparent: bool,
+    /// Whether the tile is a flowing or still liquid.
+    pub liquid: bool,
+    /// Whether the tile falls when there is nothing below it.
+    pub gravity: bool,
+    /// Whether touching the tile hurts entities.
+    pub harmful: bool,
+    /// The level of light emitted by the tile, from 0 to 15.
+    pub light: u8,
+    /// Whether the tile breaks when the block it is attached to is removed.
+    pub needs_support: bool,
+    /// The [`TileData`] values that are meaningful for the tile.
+    pub data: RangeInclusive<u8>,
+}
+
+impl BlockProperties {
+    /// An opaque, full-sized block without any state.
+    const SOLID: Self = Self {
+        solid: true,
+        transparent: false,
+        liquid: false,
+        gravity: false,
+        harmful: false,
+        light: 0,
+        needs_support: false,
+        data: 0..=0,
+    };
+
+    /// A solid block that can be seen through, such as glass or stairs.
+    const SEE_THROUGH: Self = Self {
+        transparent: true,
+        ..Self::SOLID
+    };
+
+    /// A tile without collision, such as air.
+    const EMPTY: Self = Self {
+        solid: false,
+        transparent: true,
+        ..Self::SOLID
+    };
+
+    /// A tile without collision that is attached to another block, such as a
+    /// flower or torch.
+    const DECORATION: Self = Self {
+        needs_support: true,
+        ..Self::EMPTY
+    };
+
+    const LIQUID: Self = Self {
+        liquid: true,
+        data: 0..=15,
+        ..Self::EMPTY
+    };
+
+    const fn data(self, data: RangeInclusive<u8>) -> Self {
+        Self { data, ..self }
+    }
+
+    const fn light(self, light: u8) -> Self {
+        Self { light, ..self }
+    }
+
+    const fn gravity(self) -> Self {
+        Self {
+            gravity: true,
+            ..self
+        }
+    }
+
+    const fn harmful(self) -> Self {
+        Self {
+            harmful: true,
+            ..self
+        }
+    }
+
+    const fn needs_support(self) -> Self {
+        Self {
+            needs_support: true,
+            ..self
+        }
+    }
+
+    /// Returns whether the given data is meaningful for the tile.
+    pub fn is_valid_data(&self, data: TileData) -> bool {
+        self.data.contains(&data.0)
+    }
+
+    /// Returns whether an entity can be inside the tile without colliding with
+    /// it or being hurt.
+    pub const fn is_passable(&self) -> bool {
+        !self.solid && !self.harmful
+    }
+}
+
+static REGISTRY: LazyLock<HashMap<Tile, BlockProperties>> = LazyLock::new(|| {
+    use BlockProperties as P;
+
+    HashMap::from([
+        (Tile::AIR, P::EMPTY),
+        (Tile::STONE, P::SOLID),
+        (Tile::GRASS_BLOCK, P::SOLID),
+        (Tile::DIRT, P::SOLID),
+        (Tile::COBBLESTONE, P::SOLID),
+        (Tile::PLANKS, P::SOLID),
+        (Tile::SAPLING, P::DECORATION.data(0..=15)),
+        (Tile::BEDROCK, P::SOLID),
+        (Tile::WATER, P::LIQUID),
+        (Tile::STILL_WATER, P::LIQUID),
+        (Tile::LAVA, P::LIQUID.light(15).harmful()),
+        (Tile::STILL_LAVA, P::LIQUID.light(15).harmful()),
+        (Tile::SAND, P::SOLID.gravity()),
+        (Tile::GRAVEL, P::SOLID.gravity()),
+        (Tile::GOLD_ORE, P::SOLID),
+        (Tile::IRON_ORE, P::SOLID),
+        (Tile::COAL_ORE, P::SOLID),
+        (Tile::LOG, P::SOLID.data(0..=2)),
+        (Tile::LEAVES, P::SEE_THROUGH.data(0..=15)),
+        (Tile::GLASS, P::SEE_THROUGH),
+        (Tile::LAPIS_ORE, P::SOLID),
+        (Tile::LAPIS_BLOCK, P::SOLID),
+        (Tile::SANDSTONE, P::SOLID.data(0..=2)),
+        (Tile::BED, P::SEE_THROUGH.needs_support().data(0..=11)),
+        (Tile::COBWEB, P::EMPTY),
+        (Tile::BUSH, P::DECORATION.data(0..=3)),
+        (Tile::WOOL, P::SOLID.data(0..=15)),
+        (Tile::DANDELION, P::DECORATION),
+        (Tile::BLUE_ROSE, P::DECORATION),
+        (Tile::BROWN_MUSHROOM, P::DECORATION.light(1)),
+        (Tile::RED_MUSHROOM, P::DECORATION),
+        (Tile::GOLD_BLOCK, P::SOLID),
+        (Tile::IRON_BLOCK, P::SOLID),
+        (Tile::DOUBLE_SLAB, P::SOLID.data(0..=7)),
+        (Tile::SLAB, P::SEE_THROUGH.data(0..=15)),
+        (Tile::BRICKS, P::SOLID),
+        (Tile::TNT, P::SOLID.data(0..=1)),
+        (Tile::BOOKSHELF, P::SOLID),
+        (Tile::MOSSY_COBBLESTONE, P::SOLID),
+        (Tile::OBSIDIAN, P::SOLID),
+        (Tile::TORCH, P::DECORATION.light(14).data(0..=5)),
+        (Tile::FIRE, P::DECORATION.light(15).harmful().data(0..=15)),
+        (Tile::WOODEN_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::CHEST, P::SEE_THROUGH.data(0..=5)),
+        (Tile::DIAMOND_ORE, P::SOLID),
+        (Tile::DIAMOND_BLOCK, P::SOLID),
+        (Tile::CRAFTING_TABLE, P::SOLID),
+        (Tile::WHEAT, P::DECORATION.data(0..=7)),
+        (Tile::FARMLAND, P::SEE_THROUGH.data(0..=7)),
+        (Tile::FURNACE, P::SOLID.data(0..=5)),
+        (Tile::LIT_FURNACE, P::SOLID.light(13).data(0..=5)),
+        (Tile::SIGN, P::DECORATION.data(0..=15)),
+        (
+            Tile::WOODEN_DOOR,
+            P::SEE_THROUGH.needs_support().data(0..=15),
+        ),
+        (Tile::LADDER, P::DECORATION.data(0..=5)),
+        (Tile::COBBLESTONE_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::WALL_SIGN, P::DECORATION.data(0..=5)),
+        (Tile::IRON_DOOR, P::SEE_THROUGH.needs_support().data(0..=15)),
+        (Tile::REDSTONE_ORE, P::SOLID),
+        (Tile::LIT_REDSTONE_ORE, P::SOLID.light(9)),
+        (Tile::SNOW, P::DECORATION.data(0..=7)),
+        (Tile::ICE, P::SEE_THROUGH),
+        (Tile::SNOW_BLOCK, P::SOLID),
+        (
+            Tile::CACTUS,
+            P::SEE_THROUGH.needs_support().harmful().data(0..=15),
+        ),
+        (Tile::CLAY, P::SOLID),
+        (Tile::SUGARCANE, P::DECORATION.data(0..=15)),
+        (Tile::FENCE, P::SEE_THROUGH),
+        (Tile::NETHERRACK, P::SOLID),
+        (Tile::GLOWSTONE, P::SOLID.light(15)),
+        (Tile::INVISIBLE_BEDROCK, P::SEE_THROUGH),
+        (Tile::TRAPDOOR, P::SEE_THROUGH.data(0..=7)),
+        (Tile::STONE_BRICKS, P::SOLID.data(0..=2)),
+        (Tile::GLASS_PANE, P::SEE_THROUGH),
+        (Tile::MELON, P::SOLID),
+        (Tile::MELON_STEM, P::DECORATION.data(0..=7)),
+        (Tile::FENCE_GATE, P::SEE_THROUGH.data(0..=7)),
+        (Tile::BRICK_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::STONE_BRICK_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::NETHER_BRICKS, P::SOLID),
+        (Tile::NETHER_BRICK_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::SANDSTONE_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::QUARTZ, P::SOLID.data(0..=4)),
+        (Tile::QUARTZ_STAIRS, P::SEE_THROUGH.data(0..=7)),
+        (Tile::STONECUTTER, P::SOLID),
+        (Tile::GLOWING_OBSIDIAN, P::SOLID),
+        (Tile::NETHER_REACTOR_CORE, P::SOLID.data(0..=2)),
+        (Tile::UPDATE, P::SOLID),
+        (Tile::ATEUPD, P::SOLID),
+        (Tile::GRASS_BLOCK_CARRIED, P::SOLID),
+        (Tile::LEAVES_CARRIED, P::SEE_THROUGH.data(0..=15)),
+        (Tile::STONE_1, P::SOLID),
+    ])
+});
+
+impl Tile {
+    /// Returns how the tile behaves, or [`None`] if it is not a vanilla tile.
+    pub fn properties(self) -> Option<&'static BlockProperties> {
+        REGISTRY.get(&self)
+    }
+}
+
+/// Returns whether a player can safely stand in a two block tall space.
+///
+/// The `ground` tile must be solid, while the `feet` and `head` tiles must be
+/// passable. Tiles without known properties are treated as unsafe.
+pub fn can_stand_on(ground: Tile, feet: Tile, head: Tile) -> bool {
+    let is_passable = |tile: Tile| tile.properties().is_some_and(BlockProperties::is_passable);
+    ground
+        .properties()
+        .is_some_and(|ground| ground.solid && !ground.harmful)
+        && is_passable(feet)
+        && is_passable(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_properties() {
+        let sand = Tile::SAND.properties().unwrap();
+        assert!(sand.solid && sand.gravity);
+        assert_eq!(Tile::GLOWSTONE.properties().unwrap().light, 15);
+        assert!(Tile::STILL_WATER.properties().unwrap().liquid);
+        assert!(Tile::TORCH.properties().unwrap().needs_support);
+        assert!(Tile::WOOL
+            .properties()
+            .unwrap()
+            .is_valid_data(TileData::BLACK));
+        assert!(!Tile::STONE.properties().unwrap().is_valid_data(TileData(1)));
+        assert_eq!(Tile(200).properties(), None);
+    }
+
+    #[test]
+    fn block_can_stand_on() {
+        assert!(can_stand_on(Tile::GRASS_BLOCK, Tile::AIR, Tile::AIR));
+        assert!(can_stand_on(Tile::STONE, Tile::TORCH, Tile::AIR));
+        assert!(!can_stand_on(Tile::WATER, Tile::AIR, Tile::AIR));
+        assert!(!can_stand_on(Tile::STONE, Tile::AIR, Tile::STONE));
+        assert!(!can_stand_on(Tile::STONE, Tile::LAVA, Tile::AIR));
+        assert!(!can_stand_on(Tile(200), Tile::AIR, Tile::AIR));
+    }
+}
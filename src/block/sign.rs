@@ -0,0 +1,22 @@
+//! Limits on the text that can be written to signs.
+
+use crate::{InvalidSignLineSnafu, Result};
+
+/// The number of lines of text that fit on a sign.
+pub const MAX_SIGN_LINES: usize = 4;
+/// The number of characters that fit on each line of a sign.
+pub const MAX_SIGN_LINE_LENGTH: usize = 15;
+
+/// Checks that a line of text can be written to a sign.
+pub(crate) fn validate_sign_line(line: &str, allow_commas: bool) -> Result {
+    let reason = if line.chars().count() > MAX_SIGN_LINE_LENGTH {
+        format!("longer than {MAX_SIGN_LINE_LENGTH} characters")
+    } else if line.contains(['\n', '\r']) {
+        "contains a line break".to_owned()
+    } else if !allow_commas && line.contains(',') {
+        "commas are not supported by Raspberry Juice servers".to_owned()
+    } else {
+        return Ok(());
+    };
+    InvalidSignLineSnafu { line, reason }.fail()
+}
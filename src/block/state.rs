@@ -0,0 +1,724 @@
+//! Typed views of the [`TileData`] of common block families.
+
+use std::fmt;
+
+use crate::block::{Block, BlockFace};
+use crate::connection::{SheepColor, Tile, TileData};
+
+/// A horizontal direction that a block can face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Facing {
+    PositiveX,
+    NegativeX,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl fmt::Display for Facing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PositiveX => "+X",
+            Self::NegativeX => "-X",
+            Self::PositiveZ => "+Z",
+            Self::NegativeZ => "-Z",
+        })
+    }
+}
+
+impl From<Facing> for BlockFace {
+    fn from(facing: Facing) -> Self {
+        match facing {
+            Facing::PositiveX => Self::PositiveX,
+            Facing::NegativeX => Self::NegativeX,
+            Facing::PositiveZ => Self::PositiveZ,
+            Facing::NegativeZ => Self::NegativeZ,
+        }
+    }
+}
+
+impl TryFrom<BlockFace> for Facing {
+    type Error = BlockFace;
+
+    /// Fails with the original face if it is the top or bottom face.
+    fn try_from(face: BlockFace) -> Result<Self, Self::Error> {
+        Ok(match face {
+            BlockFace::PositiveX => Self::PositiveX,
+            BlockFace::NegativeX => Self::NegativeX,
+            BlockFace::PositiveZ => Self::PositiveZ,
+            BlockFace::NegativeZ => Self::NegativeZ,
+            face => return Err(face),
+        })
+    }
+}
+
+/// One of the 16 colors shared by wool and sheep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    White,
+    Orange,
+    Magenta,
+    LightBlue,
+    Yellow,
+    Lime,
+    Pink,
+    Gray,
+    LightGray,
+    Cyan,
+    Purple,
+    Blue,
+    Brown,
+    Green,
+    Red,
+    Black,
+}
+
+impl Color {
+    /// Every color, ordered by ID.
+    pub const ALL: [Self; 16] = [
+        Self::White,
+        Self::Orange,
+        Self::Magenta,
+        Self::LightBlue,
+        Self::Yellow,
+        Self::Lime,
+        Self::Pink,
+        Self::Gray,
+        Self::LightGray,
+        Self::Cyan,
+        Self::Purple,
+        Self::Blue,
+        Self::Brown,
+        Self::Green,
+        Self::Red,
+        Self::Black,
+    ];
+
+    /// Returns the color with the given ID, which is also the [`TileData`] of
+    /// the matching wool block.
+    pub const fn from_id(id: u8) -> Option<Self> {
+        if id < 16 {
+            Some(Self::ALL[id as usize])
+        } else {
+            None
+        }
+    }
+
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::White => "White",
+            Self::Orange => "Orange",
+            Self::Magenta => "Magenta",
+            Self::LightBlue => "Light Blue",
+            Self::Yellow => "Yellow",
+            Self::Lime => "Lime",
+            Self::Pink => "Pink",
+            Self::Gray => "Gray",
+            Self::LightGray => "Light Gray",
+            Self::Cyan => "Cyan",
+            Self::Purple => "Purple",
+            Self::Blue => "Blue",
+            Self::Brown => "Brown",
+            Self::Green => "Green",
+            Self::Red => "Red",
+            Self::Black => "Black",
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Converts through the color's wool [`TileData`], which has the same palette
+/// as sheep.
+impl From<Color> for SheepColor {
+    fn from(color: Color) -> Self {
+        Self::try_from(TileData::from(color)).expect("every color is a wool color")
+    }
+}
+
+impl TryFrom<SheepColor> for Color {
+    type Error = SheepColor;
+
+    /// Fails with the original color if it has no matching [`Color`].
+    fn try_from(color: SheepColor) -> Result<Self, Self::Error> {
+        TileData::try_from(color)
+            .ok()
+            .and_then(|data| Self::from_id(data.0))
+            .ok_or(color)
+    }
+}
+
+impl From<Color> for TileData {
+    fn from(color: Color) -> Self {
+        Self(color.id())
+    }
+}
+
+/// The material of a slab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlabMaterial {
+    Stone,
+    Sandstone,
+    Wood,
+    Cobblestone,
+    Bricks,
+    StoneBricks,
+    PolishedStone,
+}
+
+impl SlabMaterial {
+    /// Every material, ordered by ID.
+    pub const ALL: [Self; 7] = [
+        Self::Stone,
+        Self::Sandstone,
+        Self::Wood,
+        Self::Cobblestone,
+        Self::Bricks,
+        Self::StoneBricks,
+        Self::PolishedStone,
+    ];
+
+    const fn from_id(id: u8) -> Option<Self> {
+        if id < 7 {
+            Some(Self::ALL[id as usize])
+        } else {
+            None
+        }
+    }
+
+    const fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Stone => "Stone",
+            Self::Sandstone => "Sandstone",
+            Self::Wood => "Wooden",
+            Self::Cobblestone => "Cobblestone",
+            Self::Bricks => "Brick",
+            Self::StoneBricks => "Stone Brick",
+            Self::PolishedStone => "Polished Stone",
+        }
+    }
+}
+
+/// The material of a set of stairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StairsMaterial {
+    Wood,
+    Cobblestone,
+    Brick,
+    StoneBrick,
+    NetherBrick,
+    Sandstone,
+    Quartz,
+}
+
+impl StairsMaterial {
+    /// Every material.
+    pub const ALL: [Self; 7] = [
+        Self::Wood,
+        Self::Cobblestone,
+        Self::Brick,
+        Self::StoneBrick,
+        Self::NetherBrick,
+        Self::Sandstone,
+        Self::Quartz,
+    ];
+
+    /// Returns the stairs tile made of this material.
+    pub const fn tile(self) -> Tile {
+        match self {
+            Self::Wood => Tile::WOODEN_STAIRS,
+            Self::Cobblestone => Tile::COBBLESTONE_STAIRS,
+            Self::Brick => Tile::BRICK_STAIRS,
+            Self::StoneBrick => Tile::STONE_BRICK_STAIRS,
+            Self::NetherBrick => Tile::NETHER_BRICK_STAIRS,
+            Self::Sandstone => Tile::SANDSTONE_STAIRS,
+            Self::Quartz => Tile::QUARTZ_STAIRS,
+        }
+    }
+
+    /// Returns the material of a stairs tile.
+    pub fn from_tile(tile: Tile) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|material| material.tile() == tile)
+    }
+}
+
+/// The material of a door.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DoorMaterial {
+    Wood,
+    Iron,
+}
+
+impl DoorMaterial {
+    /// Returns the door tile made of this material.
+    pub const fn tile(self) -> Tile {
+        match self {
+            Self::Wood => Tile::WOODEN_DOOR,
+            Self::Iron => Tile::IRON_DOOR,
+        }
+    }
+
+    /// Returns the material of a door tile.
+    pub const fn from_tile(tile: Tile) -> Option<Self> {
+        match tile {
+            Tile::WOODEN_DOOR => Some(Self::Wood),
+            Tile::IRON_DOOR => Some(Self::Iron),
+            _ => None,
+        }
+    }
+}
+
+/// The side of a door that its hinge is on, when facing the same way as the
+/// door.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DoorHinge {
+    Left,
+    Right,
+}
+
+/// A block whose [`TileData`] has been decoded into its state.
+///
+/// Converting a [`Block`] into a state and back always gives the same block.
+/// Blocks outside of the supported families, blocks with data that does not
+/// match a known state, and blocks with NBT data are kept as
+/// [`BlockState::Other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockState {
+    Wool(Color),
+    Stairs {
+        material: StairsMaterial,
+        facing: Facing,
+        upside_down: bool,
+    },
+    /// The bottom half of a door, which stores its facing and whether it is
+    /// open.
+    Door {
+        material: DoorMaterial,
+        facing: Facing,
+        open: bool,
+    },
+    /// The top half of a door, which stores the side of its hinge and whether
+    /// it is powered.
+    DoorTop {
+        material: DoorMaterial,
+        hinge: DoorHinge,
+        powered: bool,
+    },
+    Slab {
+        material: SlabMaterial,
+        top: bool,
+    },
+    DoubleSlab(SlabMaterial),
+    Chest {
+        facing: Option<Facing>,
+    },
+    Ladder {
+        facing: Facing,
+    },
+    WallSign {
+        facing: Facing,
+    },
+    /// One half of a bed, facing from its foot towards its head.
+    Bed {
+        facing: Facing,
+        head: bool,
+    },
+    /// A trapdoor, facing away from the block it is attached to.
+    Trapdoor {
+        facing: Facing,
+        open: bool,
+    },
+    FenceGate {
+        facing: Facing,
+        open: bool,
+    },
+    Furnace {
+        facing: Facing,
+        lit: bool,
+    },
+    /// A torch, facing away from the wall it is attached to, or standing on
+    /// the ground if it has no facing.
+    Torch {
+        facing: Option<Facing>,
+    },
+    Other(Block),
+}
+
+/// Facing of stairs, indexed by the lowest two bits of their data.
+const STAIRS_FACING: [Facing; 4] = [
+    Facing::PositiveX,
+    Facing::NegativeX,
+    Facing::PositiveZ,
+    Facing::NegativeZ,
+];
+
+/// Facing of doors, indexed by the lowest two bits of their data.
+const DOOR_FACING: [Facing; 4] = [
+    Facing::NegativeX,
+    Facing::NegativeZ,
+    Facing::PositiveX,
+    Facing::PositiveZ,
+];
+
+/// Facing of chests, ladders and furnaces, indexed by data minus two.
+const ATTACHED_FACING: [Facing; 4] = [
+    Facing::NegativeZ,
+    Facing::PositiveZ,
+    Facing::NegativeX,
+    Facing::PositiveX,
+];
+
+/// Facing of beds and fence gates, indexed by the lowest two bits of their
+/// data.
+const BED_FACING: [Facing; 4] = [
+    Facing::PositiveZ,
+    Facing::NegativeX,
+    Facing::NegativeZ,
+    Facing::PositiveX,
+];
+
+/// Facing of trapdoors, indexed by the lowest two bits of their data.
+const TRAPDOOR_FACING: [Facing; 4] = [
+    Facing::PositiveZ,
+    Facing::NegativeZ,
+    Facing::PositiveX,
+    Facing::NegativeX,
+];
+
+/// Facing of torches attached to walls, indexed by data minus one.
+const TORCH_FACING: [Facing; 4] = [
+    Facing::PositiveX,
+    Facing::NegativeX,
+    Facing::PositiveZ,
+    Facing::NegativeZ,
+];
+
+fn index_of(facings: &[Facing; 4], facing: Facing) -> u8 {
+    facings
+        .iter()
+        .position(|&f| f == facing)
+        .unwrap_or_default() as u8
+}
+
+impl BlockState {
+    fn decode(tile: Tile, data: u8) -> Option<Self> {
+        Some(match tile {
+            Tile::WOOL => Self::Wool(Color::from_id(data)?),
+            tile if data < 8 && StairsMaterial::from_tile(tile).is_some() => Self::Stairs {
+                material: StairsMaterial::from_tile(tile)?,
+                facing: STAIRS_FACING[usize::from(data & 3)],
+                upside_down: data & 4 != 0,
+            },
+            Tile::WOODEN_DOOR | Tile::IRON_DOOR if data < 8 => Self::Door {
+                material: DoorMaterial::from_tile(tile)?,
+                facing: DOOR_FACING[usize::from(data & 3)],
+                open: data & 4 == 0,
+            },
+            Tile::WOODEN_DOOR | Tile::IRON_DOOR if data < 12 => Self::DoorTop {
+                material: DoorMaterial::from_tile(tile)?,
+                hinge: if data & 1 == 0 {
+                    DoorHinge::Left
+                } else {
+                    DoorHinge::Right
+                },
+                powered: data & 2 != 0,
+            },
+            Tile::SLAB => Self::Slab {
+                material: SlabMaterial::from_id(data & 7)?,
+                top: data & 8 != 0,
+            },
+            Tile::DOUBLE_SLAB => Self::DoubleSlab(SlabMaterial::from_id(data)?),
+            Tile::CHEST if data == 0 => Self::Chest { facing: None },
+            Tile::CHEST if (2..=5).contains(&data) => Self::Chest {
+                facing: Some(ATTACHED_FACING[usize::from(data - 2)]),
+            },
+            Tile::LADDER if (2..=5).contains(&data) => Self::Ladder {
+                facing: ATTACHED_FACING[usize::from(data - 2)],
+            },
+            Tile::WALL_SIGN => Self::WallSign {
+                facing: match data {
+                    0 => Facing::PositiveZ,
+                    2 => Facing::NegativeZ,
+                    4 => Facing::NegativeX,
+                    5 => Facing::PositiveX,
+                    _ => return None,
+                },
+            },
+            // Beds that are occupied set bit 4, which is not decoded.
+            Tile::BED if data & 4 == 0 => Self::Bed {
+                facing: BED_FACING[usize::from(data & 3)],
+                head: data & 8 != 0,
+            },
+            Tile::TRAPDOOR if data < 8 => Self::Trapdoor {
+                facing: TRAPDOOR_FACING[usize::from(data & 3)],
+                open: data & 4 != 0,
+            },
+            Tile::FENCE_GATE if data < 8 => Self::FenceGate {
+                facing: BED_FACING[usize::from(data & 3)],
+                open: data & 4 != 0,
+            },
+            Tile::FURNACE | Tile::LIT_FURNACE if (2..=5).contains(&data) => Self::Furnace {
+                facing: ATTACHED_FACING[usize::from(data - 2)],
+                lit: tile == Tile::LIT_FURNACE,
+            },
+            Tile::TORCH if (1..=4).contains(&data) => Self::Torch {
+                facing: Some(TORCH_FACING[usize::from(data - 1)]),
+            },
+            Tile::TORCH if data == 5 => Self::Torch { facing: None },
+            _ => return None,
+        })
+    }
+
+    /// Returns the tile of the block.
+    pub const fn tile(&self) -> Tile {
+        match self {
+            Self::Wool(_) => Tile::WOOL,
+            Self::Stairs { material, .. } => material.tile(),
+            Self::Door { material, .. } | Self::DoorTop { material, .. } => material.tile(),
+            Self::Slab { .. } => Tile::SLAB,
+            Self::DoubleSlab(_) => Tile::DOUBLE_SLAB,
+            Self::Chest { .. } => Tile::CHEST,
+            Self::Ladder { .. } => Tile::LADDER,
+            Self::WallSign { .. } => Tile::WALL_SIGN,
+            Self::Bed { .. } => Tile::BED,
+            Self::Trapdoor { .. } => Tile::TRAPDOOR,
+            Self::FenceGate { .. } => Tile::FENCE_GATE,
+            Self::Furnace { lit: false, .. } => Tile::FURNACE,
+            Self::Furnace { lit: true, .. } => Tile::LIT_FURNACE,
+            Self::Torch { .. } => Tile::TORCH,
+            Self::Other(block) => block.tile,
+        }
+    }
+
+    /// Encodes the state as [`TileData`].
+    pub fn data(&self) -> TileData {
+        TileData(match *self {
+            Self::Wool(color) => color.id(),
+            Self::Stairs {
+                facing,
+                upside_down,
+                ..
+            } => index_of(&STAIRS_FACING, facing) | if upside_down { 4 } else { 0 },
+            Self::Door { facing, open, .. } => {
+                index_of(&DOOR_FACING, facing) | if open { 0 } else { 4 }
+            }
+            Self::DoorTop { hinge, powered, .. } => {
+                8 | if hinge == DoorHinge::Right { 1 } else { 0 } | if powered { 2 } else { 0 }
+            }
+            Self::Slab { material, top } => material.id() | if top { 8 } else { 0 },
+            Self::DoubleSlab(material) => material.id(),
+            Self::Chest { facing: None } => 0,
+            Self::Chest {
+                facing: Some(facing),
+            }
+            | Self::Ladder { facing }
+            | Self::Furnace { facing, .. } => index_of(&ATTACHED_FACING, facing) + 2,
+            Self::WallSign { facing } => match facing {
+                Facing::PositiveZ => 0,
+                Facing::NegativeZ => 2,
+                Facing::NegativeX => 4,
+                Facing::PositiveX => 5,
+            },
+            Self::Bed { facing, head } => index_of(&BED_FACING, facing) | if head { 8 } else { 0 },
+            Self::Trapdoor { facing, open } => {
+                index_of(&TRAPDOOR_FACING, facing) | if open { 4 } else { 0 }
+            }
+            Self::FenceGate { facing, open } => {
+                index_of(&BED_FACING, facing) | if open { 4 } else { 0 }
+            }
+            Self::Torch {
+                facing: Some(facing),
+            } => index_of(&TORCH_FACING, facing) + 1,
+            Self::Torch { facing: None } => 5,
+            Self::Other(ref block) => block.data.0,
+        })
+    }
+}
+
+impl From<Block> for BlockState {
+    fn from(block: Block) -> Self {
+        if block.nbt.is_some() {
+            return Self::Other(block);
+        }
+        Self::decode(block.tile, block.data.0).unwrap_or(Self::Other(block))
+    }
+}
+
+impl From<BlockState> for Block {
+    fn from(state: BlockState) -> Self {
+        match state {
+            BlockState::Other(block) => block,
+            state => Self::new(state.tile(), state.data()),
+        }
+    }
+}
+
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wool(color) => write!(f, "{color} Wool"),
+            Self::Stairs {
+                material,
+                facing,
+                upside_down,
+            } => {
+                write!(f, "{} (facing {facing}", material.tile().display())?;
+                if *upside_down {
+                    write!(f, ", upside down")?;
+                }
+                write!(f, ")")
+            }
+            Self::Door {
+                material,
+                facing,
+                open,
+            } => {
+                let open = if *open { "open" } else { "closed" };
+                let tile = material.tile().display();
+                write!(f, "{tile} (bottom, facing {facing}, {open})")
+            }
+            Self::DoorTop {
+                material,
+                hinge,
+                powered,
+            } => {
+                let hinge = match hinge {
+                    DoorHinge::Left => "left",
+                    DoorHinge::Right => "right",
+                };
+                write!(f, "{} (top, {hinge} hinge", material.tile().display())?;
+                if *powered {
+                    write!(f, ", powered")?;
+                }
+                write!(f, ")")
+            }
+            Self::Slab { material, top } => {
+                let half = if *top { "top" } else { "bottom" };
+                write!(f, "{} Slab ({half})", material.name())
+            }
+            Self::DoubleSlab(material) => write!(f, "{} Double Slab", material.name()),
+            Self::Chest { facing: None } => write!(f, "Chest"),
+            Self::Chest {
+                facing: Some(facing),
+            } => write!(f, "Chest (facing {facing})"),
+            Self::Ladder { facing } => write!(f, "Ladder (facing {facing})"),
+            Self::WallSign { facing } => write!(f, "Wall Sign (facing {facing})"),
+            Self::Bed { facing, head } => {
+                let part = if *head { "head" } else { "foot" };
+                write!(f, "Bed ({part}, facing {facing})")
+            }
+            Self::Trapdoor { facing, open } | Self::FenceGate { facing, open } => {
+                let open = if *open { "open" } else { "closed" };
+                write!(f, "{} (facing {facing}, {open})", self.tile().display())
+            }
+            Self::Furnace { facing, .. } => {
+                write!(f, "{} (facing {facing})", self.tile().display())
+            }
+            Self::Torch { facing: None } => write!(f, "Torch (standing)"),
+            Self::Torch {
+                facing: Some(facing),
+            } => write!(f, "Torch (facing {facing})"),
+            Self::Other(block) => write!(f, "{} ({})", block.tile.display(), block.data),
+        }
+    }
+}
+
+impl Block {
+    /// Decodes the block's data into a typed state.
+    pub fn state(&self) -> BlockState {
+        self.clone().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_state_round_trips() {
+        for tile in 0..=255 {
+            for data in 0..16 {
+                let block = Block::new(Tile(tile), TileData(data));
+                let state = BlockState::from(block.clone());
+                assert_eq!(Block::from(state), block);
+            }
+        }
+    }
+
+    #[test]
+    fn block_state_decodes_tile_data() {
+        assert_eq!(
+            Block::new(Tile::WOOL, TileData::RED).state(),
+            BlockState::Wool(Color::Red)
+        );
+        assert_eq!(
+            Block::new(Tile::BRICK_STAIRS, TileData::STAIRS_Z_NEGATIVE_UPSIDE_DOWN).state(),
+            BlockState::Stairs {
+                material: StairsMaterial::Brick,
+                facing: Facing::NegativeZ,
+                upside_down: true,
+            }
+        );
+        assert_eq!(
+            Block::new(Tile::IRON_DOOR, TileData::DOOR_CLOSED_BOTTOM_X_POSITIVE).state(),
+            BlockState::Door {
+                material: DoorMaterial::Iron,
+                facing: Facing::PositiveX,
+                open: false,
+            }
+        );
+        assert_eq!(
+            Block::new(Tile::WOODEN_DOOR, TileData(11)).state(),
+            BlockState::DoorTop {
+                material: DoorMaterial::Wood,
+                hinge: DoorHinge::Right,
+                powered: true,
+            }
+        );
+        assert!(matches!(
+            Block::new(Tile::WOODEN_DOOR, TileData(12)).state(),
+            BlockState::Other(_)
+        ));
+        assert_eq!(
+            Block::new(Tile::SLAB, TileData::SLAB_WOOD_TOP).state(),
+            BlockState::Slab {
+                material: SlabMaterial::Wood,
+                top: true,
+            }
+        );
+        assert!(matches!(
+            Block::new(Tile::STONE, TileData(3)).state(),
+            BlockState::Other(_)
+        ));
+    }
+
+    #[test]
+    fn block_state_display() {
+        assert_eq!(
+            BlockState::Wool(Color::LightBlue).to_string(),
+            "Light Blue Wool"
+        );
+        let stairs = Block::new(Tile::WOODEN_STAIRS, TileData::STAIRS_X_POSITIVE).state();
+        assert_eq!(stairs.to_string(), "Wooden Stairs (facing +X)");
+    }
+
+    #[test]
+    fn color_matches_sheep_color() {
+        assert_eq!(SheepColor::from(Color::Red), SheepColor::RED);
+        assert_eq!(Color::try_from(SheepColor::LIME), Ok(Color::Lime));
+        assert_eq!(Color::try_from(SheepColor(16)), Err(SheepColor(16)));
+    }
+}
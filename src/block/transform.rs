@@ -0,0 +1,321 @@
+//! Rotating and mirroring blocks whose [`TileData`] encodes a direction.
+
+use crate::block::state::{BlockState, DoorHinge, Facing};
+use crate::block::Block;
+use crate::connection::{Tile, TileData};
+
+/// A horizontal axis that blocks can be mirrored along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Flips X coordinates, swapping east and west.
+    X,
+    /// Flips Z coordinates, swapping north and south.
+    Z,
+}
+
+/// Horizontal directions in clockwise order, when seen from above.
+const CLOCKWISE: [Facing; 4] = [
+    Facing::PositiveX,
+    Facing::PositiveZ,
+    Facing::NegativeX,
+    Facing::NegativeZ,
+];
+
+impl Facing {
+    /// Rotates the direction clockwise (when seen from above) by the given
+    /// number of 90° turns. Negative turns rotate counterclockwise.
+    pub fn rotate_y(self, quarter_turns: i32) -> Self {
+        let index = CLOCKWISE
+            .iter()
+            .position(|&f| f == self)
+            .unwrap_or_default();
+        CLOCKWISE[(index as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    /// Mirrors the direction along the given axis.
+    pub const fn mirror(self, axis: Axis) -> Self {
+        match (self, axis) {
+            (Self::PositiveX, Axis::X) => Self::NegativeX,
+            (Self::NegativeX, Axis::X) => Self::PositiveX,
+            (Self::PositiveZ, Axis::Z) => Self::NegativeZ,
+            (Self::NegativeZ, Axis::Z) => Self::PositiveZ,
+            (facing, _) => facing,
+        }
+    }
+}
+
+impl BlockState {
+    /// Applies a transformation to the direction the block faces, if it has
+    /// one.
+    fn map_facing(self, transform: impl Fn(Facing) -> Facing) -> Self {
+        match self {
+            Self::Stairs {
+                material,
+                facing,
+                upside_down,
+            } => Self::Stairs {
+                material,
+                facing: transform(facing),
+                upside_down,
+            },
+            Self::Door {
+                material,
+                facing,
+                open,
+            } => Self::Door {
+                material,
+                facing: transform(facing),
+                open,
+            },
+            Self::Chest { facing } => Self::Chest {
+                facing: facing.map(transform),
+            },
+            Self::Ladder { facing } => Self::Ladder {
+                facing: transform(facing),
+            },
+            Self::WallSign { facing } => Self::WallSign {
+                facing: transform(facing),
+            },
+            Self::Bed { facing, head } => Self::Bed {
+                facing: transform(facing),
+                head,
+            },
+            Self::Trapdoor { facing, open } => Self::Trapdoor {
+                facing: transform(facing),
+                open,
+            },
+            Self::FenceGate { facing, open } => Self::FenceGate {
+                facing: transform(facing),
+                open,
+            },
+            Self::Furnace { facing, lit } => Self::Furnace {
+                facing: transform(facing),
+                lit,
+            },
+            Self::Torch { facing } => Self::Torch {
+                facing: facing.map(transform),
+            },
+            state => state,
+        }
+    }
+
+    /// Rotates the block clockwise (when seen from above) by the given number
+    /// of 90° turns.
+    pub fn rotate_y(self, quarter_turns: i32) -> Self {
+        self.map_facing(|facing| facing.rotate_y(quarter_turns))
+    }
+
+    /// Mirrors the block along the given axis.
+    ///
+    /// Mirroring a door also moves its hinge to the other side.
+    pub fn mirror(self, axis: Axis) -> Self {
+        match self {
+            Self::DoorTop {
+                material,
+                hinge,
+                powered,
+            } => Self::DoorTop {
+                material,
+                hinge: match hinge {
+                    DoorHinge::Left => DoorHinge::Right,
+                    DoorHinge::Right => DoorHinge::Left,
+                },
+                powered,
+            },
+            state => state.map_facing(|facing| facing.mirror(axis)),
+        }
+    }
+}
+
+impl Block {
+    /// Rotates the block clockwise (when seen from above) by the given number
+    /// of 90° turns, updating the direction stored in its data.
+    ///
+    /// Stairs, doors, chests, ladders, beds, trapdoors, fence gates,
+    /// furnaces, torches, and wall and standing signs are rotated. Other
+    /// blocks are returned unchanged.
+    #[must_use]
+    pub fn rotate_y(&self, quarter_turns: i32) -> Self {
+        if self.tile == Tile::SIGN {
+            // Standing signs have 16 directions, four per quarter turn.
+            let data = (i32::from(self.data.0) + quarter_turns * 4).rem_euclid(16);
+            return self.with_data(TileData(data as u8));
+        }
+        self.map_state(|state| state.rotate_y(quarter_turns))
+    }
+
+    /// Mirrors the block along the given axis, updating the direction stored
+    /// in its data. See [`Self::rotate_y`] for the blocks that are affected.
+    #[must_use]
+    pub fn mirror(&self, axis: Axis) -> Self {
+        if self.tile == Tile::SIGN {
+            let mirrored = match axis {
+                Axis::X => 16 - i32::from(self.data.0),
+                Axis::Z => 8 - i32::from(self.data.0),
+            };
+            return self.with_data(TileData(mirrored.rem_euclid(16) as u8));
+        }
+        self.map_state(|state| state.mirror(axis))
+    }
+
+    /// Transforms the block's decoded state, keeping its NBT data.
+    fn map_state(&self, transform: impl FnOnce(BlockState) -> BlockState) -> Self {
+        let state = BlockState::from(Self::new(self.tile, self.data));
+        self.with_data(transform(state).data())
+    }
+
+    fn with_data(&self, data: TileData) -> Self {
+        Self {
+            data,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::state::DoorMaterial;
+
+    #[test]
+    fn facing_rotate_y() {
+        assert_eq!(Facing::PositiveX.rotate_y(1), Facing::PositiveZ);
+        assert_eq!(Facing::PositiveX.rotate_y(-1), Facing::NegativeZ);
+        assert_eq!(Facing::NegativeZ.rotate_y(6), Facing::PositiveZ);
+    }
+
+    #[test]
+    fn block_rotate_y() {
+        let stairs = Block::new(Tile::WOODEN_STAIRS, TileData::STAIRS_X_POSITIVE_UPSIDE_DOWN);
+        assert_eq!(
+            stairs.rotate_y(1),
+            Block::new(Tile::WOODEN_STAIRS, TileData::STAIRS_Z_POSITIVE_UPSIDE_DOWN)
+        );
+        let ladder = Block::new(Tile::LADDER, TileData::LADDER_X_NEGATIVE);
+        assert_eq!(
+            ladder.rotate_y(2),
+            Block::new(Tile::LADDER, TileData::LADDER_X_POSITIVE)
+        );
+        let sign = Block::new(Tile::SIGN, TileData::SIGN_X_POSITIVE);
+        assert_eq!(
+            sign.rotate_y(1),
+            Block::new(Tile::SIGN, TileData::SIGN_Z_POSITIVE)
+        );
+        let stone = Block::from_tile(Tile::STONE);
+        assert_eq!(stone.rotate_y(1), stone);
+    }
+
+    #[test]
+    fn block_mirror() {
+        let chest = Block::new(Tile::CHEST, TileData::CHEST_X_POSITIVE)
+            .with_nbt(serde_json::json!({ "Items": [] }));
+        let mirrored = chest.mirror(Axis::X);
+        assert_eq!(mirrored.data, TileData::CHEST_X_NEGATIVE);
+        assert_eq!(mirrored.nbt, chest.nbt);
+
+        let sign = Block::new(Tile::SIGN, TileData::SIGN_Z_POSITIVE);
+        assert_eq!(sign.mirror(Axis::Z).data, TileData::SIGN_Z_NEGATIVE);
+        assert_eq!(sign.mirror(Axis::X).data, TileData::SIGN_Z_POSITIVE);
+    }
+
+    #[test]
+    fn block_state_rotate_and_mirror() {
+        let bed = BlockState::Bed {
+            facing: Facing::PositiveZ,
+            head: true,
+        };
+        assert_eq!(
+            bed.clone().rotate_y(1),
+            BlockState::Bed {
+                facing: Facing::NegativeX,
+                head: true,
+            }
+        );
+        assert_eq!(
+            bed.mirror(Axis::Z),
+            BlockState::Bed {
+                facing: Facing::NegativeZ,
+                head: true,
+            }
+        );
+
+        let trapdoor = Block::new(Tile::TRAPDOOR, TileData::TRAPDOOR_OPENED_Z_POSITIVE);
+        assert_eq!(
+            trapdoor.rotate_y(1).data,
+            TileData::TRAPDOOR_OPENED_X_NEGATIVE
+        );
+        assert_eq!(
+            trapdoor.mirror(Axis::Z).data,
+            TileData::TRAPDOOR_OPENED_Z_NEGATIVE
+        );
+
+        let fence_gate = BlockState::FenceGate {
+            facing: Facing::PositiveX,
+            open: true,
+        };
+        assert_eq!(
+            fence_gate.clone().rotate_y(-1),
+            BlockState::FenceGate {
+                facing: Facing::NegativeZ,
+                open: true,
+            }
+        );
+        assert_eq!(
+            fence_gate.mirror(Axis::X),
+            BlockState::FenceGate {
+                facing: Facing::NegativeX,
+                open: true,
+            }
+        );
+
+        let furnace = Block::from(BlockState::Furnace {
+            facing: Facing::NegativeZ,
+            lit: true,
+        });
+        assert_eq!(
+            furnace.rotate_y(2).state(),
+            BlockState::Furnace {
+                facing: Facing::PositiveZ,
+                lit: true,
+            }
+        );
+        assert_eq!(furnace.mirror(Axis::X), furnace);
+
+        let torch = BlockState::Torch {
+            facing: Some(Facing::PositiveX),
+        };
+        assert_eq!(
+            torch.clone().rotate_y(1),
+            BlockState::Torch {
+                facing: Some(Facing::PositiveZ)
+            }
+        );
+        assert_eq!(
+            torch.mirror(Axis::X),
+            BlockState::Torch {
+                facing: Some(Facing::NegativeX)
+            }
+        );
+        let standing = BlockState::Torch { facing: None };
+        assert_eq!(standing.clone().rotate_y(1), standing);
+        assert_eq!(standing.clone().mirror(Axis::Z), standing);
+    }
+
+    #[test]
+    fn door_mirror_moves_hinge() {
+        let top = BlockState::DoorTop {
+            material: DoorMaterial::Wood,
+            hinge: DoorHinge::Left,
+            powered: false,
+        };
+        assert_eq!(top.clone().rotate_y(1), top);
+        assert_eq!(
+            top.mirror(Axis::X),
+            BlockState::DoorTop {
+                material: DoorMaterial::Wood,
+                hinge: DoorHinge::Right,
+                powered: false,
+            }
+        );
+    }
+}
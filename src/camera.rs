@@ -1,24 +1,80 @@
+use std::time::Duration;
+
 use nalgebra::Point3;
+use path::{CameraPath, PlaybackControl, PlaybackState};
+use snafu::OptionExt;
+use tokio::time::{Instant, MissedTickBehavior};
 
 use crate::connection::commands::*;
 use crate::connection::{EntityId, Protocol};
-use crate::Result;
+use crate::util::parse_point;
+use crate::{InvalidFrameRateSnafu, Result, World};
+
+pub mod path;
 
+/// A mode that controls how the camera moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CameraMode {
     Fixed,
     Follow(Option<EntityId>),
     Normal(Option<EntityId>),
     ThirdPerson(Option<EntityId>),
+    /// Raspberry Jam extension: a free-moving debug camera.
+    Debug,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Controls the game's camera.
+///
+/// Camera commands differ between servers; the vanilla `camera.mode.*`
+/// commands are used unless the connection's [`Protocol::server_kind`] is
+/// [`ServerKind::RaspberryJam`](crate::connection::ServerKind::RaspberryJam).
+///
+/// The last mode set by any camera of a [`World`] is shared between them, and
+/// can be queried using [`Camera::mode`].
+#[derive(Debug)]
 pub struct Camera<T: Protocol> {
-    connection: T,
+    world: World<T>,
+}
+
+impl<T: Protocol> Clone for Camera<T> {
+    fn clone(&self) -> Self {
+        Self {
+            world: self.world.clone(),
+        }
+    }
 }
 
 impl<T: Protocol> Camera<T> {
-    pub const fn new(connection: T) -> Self {
-        Self { connection }
+    pub const fn new(world: World<T>) -> Self {
+        Self { world }
+    }
+
+    pub fn into_inner(self) -> World<T> {
+        self.world
+    }
+
+    async fn is_raspberry_jam(&self) -> bool {
+        self.world.server_kind().await.supports_raspberry_jam()
+    }
+
+    fn track_mode(&self, mode: CameraMode) {
+        self.world.state().camera_mode = Some(mode);
+    }
+
+    /// Returns the last mode that was set using this world's camera, or
+    /// [`None`] if it has not been changed.
+    pub fn mode(&self) -> Option<CameraMode> {
+        self.world.state().camera_mode
+    }
+
+    /// Sets the camera's mode again to the last mode returned by
+    /// [`Self::mode`], undoing changes made by other clients or by
+    /// [`Self::set_position`]. Does nothing if the mode has not been changed.
+    pub async fn restore_mode(&mut self) -> Result {
+        if let Some(mode) = self.mode() {
+            self.set_mode(mode).await?;
+        }
+        Ok(())
     }
 
     pub async fn set_mode(&mut self, mode: CameraMode) -> Result {
@@ -27,36 +83,271 @@ impl<T: Protocol> Camera<T> {
             CameraMode::Follow(target) => self.set_follow(target).await?,
             CameraMode::Normal(target) => self.set_normal(target).await?,
             CameraMode::ThirdPerson(target) => self.set_third_person(target).await?,
+            CameraMode::Debug => self.set_debug().await?,
         };
         Ok(())
     }
 
     pub async fn set_fixed(&mut self) -> Result {
-        self.connection.send(CameraModeSetFixed {}).await?;
+        self.world.send_command(CameraModeSetFixed {}).await?;
+        self.track_mode(CameraMode::Fixed);
         Ok(())
     }
 
     pub async fn set_follow(&mut self, target: Option<EntityId>) -> Result {
-        self.connection.send(CameraModeSetFollow { target }).await?;
+        if self.is_raspberry_jam().await {
+            self.world
+                .send_command(raspberry_jam::CameraSetFollow { target })
+                .await?;
+        } else {
+            self.world
+                .send_command(CameraModeSetFollow { target })
+                .await?;
+        }
+        self.track_mode(CameraMode::Follow(target));
         Ok(())
     }
 
     pub async fn set_normal(&mut self, target: Option<EntityId>) -> Result {
-        self.connection.send(CameraModeSetNormal { target }).await?;
+        if self.is_raspberry_jam().await {
+            self.world
+                .send_command(raspberry_jam::CameraSetNormal { target })
+                .await?;
+        } else {
+            self.world
+                .send_command(CameraModeSetNormal { target })
+                .await?;
+        }
+        self.track_mode(CameraMode::Normal(target));
         Ok(())
     }
 
     pub async fn set_third_person(&mut self, target: Option<EntityId>) -> Result {
-        self.connection
-            .send(CameraModeSetThirdPerson { target })
+        if self.is_raspberry_jam().await {
+            self.world
+                .send_command(raspberry_jam::CameraSetThirdPerson { target })
+                .await?;
+        } else {
+            self.world
+                .send_command(CameraModeSetThirdPerson { target })
+                .await?;
+        }
+        self.track_mode(CameraMode::ThirdPerson(target));
+        Ok(())
+    }
+
+    /// Switches to a free-moving debug camera.
+    ///
+    /// Raspberry Jam server only!
+    pub async fn set_debug(&mut self) -> Result {
+        self.world.require_raspberry_jam().await?;
+        self.world
+            .send_command(raspberry_jam::CameraSetDebug {})
+            .await?;
+        self.track_mode(CameraMode::Debug);
+        Ok(())
+    }
+
+    /// Sets the distance between the camera and the entity it is following in
+    /// third person.
+    ///
+    /// Raspberry Jam server only!
+    pub async fn set_distance(&mut self, distance: f32) -> Result {
+        self.world.require_raspberry_jam().await?;
+        self.world
+            .send_command(raspberry_jam::CameraSetDistance { distance })
             .await?;
         Ok(())
     }
 
+    /// Gets the ID of the entity the camera is attached to.
+    ///
+    /// Raspberry Jam server only!
+    pub async fn entity_id(&self) -> Result<EntityId> {
+        self.world.require_raspberry_jam().await?;
+        let id = self
+            .world
+            .send_command(raspberry_jam::CameraGetEntityId {})
+            .await?;
+        Ok(id.parse()?)
+    }
+
     pub async fn set_position(&mut self, position: Point3<f64>) -> Result {
-        self.connection
-            .send(CameraSetPos { coords: position })
+        self.world
+            .send_command(CameraSetPos { coords: position })
             .await?;
         Ok(())
     }
+
+    /// Moves the camera along the given path, returning once the end of the
+    /// path is reached or playback is stopped using `control`.
+    ///
+    /// Looping paths only return once playback is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to play.
+    /// * `frame_rate` - The number of times per second to update the camera's
+    ///   position.
+    /// * `control` - A handle that can be used to pause, resume, or stop
+    ///   playback from another task.
+    ///
+    /// Fails with [`WorldError::InvalidFrameRate`](crate::WorldError::InvalidFrameRate)
+    /// if `frame_rate` is not a positive, finite number, or is so high that
+    /// frames would not be spaced apart.
+    pub async fn play_path(
+        &mut self,
+        path: &CameraPath,
+        frame_rate: f64,
+        control: &PlaybackControl,
+    ) -> Result {
+        let period = Duration::try_from_secs_f64(frame_rate.recip())
+            .ok()
+            .filter(|period| !period.is_zero())
+            .context(InvalidFrameRateSnafu { frame_rate })?;
+        let mut state = control.subscribe();
+        let mut frames = tokio::time::interval(period);
+        frames.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let mut elapsed = Duration::ZERO;
+        let mut last_frame = Instant::now();
+        loop {
+            frames.tick().await;
+            let now = Instant::now();
+            let current_state = *state.borrow_and_update();
+            match current_state {
+                PlaybackState::Playing => elapsed += now - last_frame,
+                PlaybackState::Paused => {
+                    // Wait for playback to resume without advancing the path.
+                    if state.changed().await.is_err() {
+                        return Ok(());
+                    }
+                    last_frame = Instant::now();
+                    continue;
+                }
+                PlaybackState::Stopped => return Ok(()),
+            }
+            last_frame = now;
+
+            let finished = !path.is_looping() && elapsed >= path.duration();
+            let Some(mut position) = path.position_at(elapsed) else {
+                return Ok(());
+            };
+            if let Some((target, offset)) = path.follow() {
+                let target_position = self.world.send_command(EntityGetPos { target }).await?;
+                let target_position: Point3<f64> = parse_point(&target_position)?;
+                position += target_position.coords + offset;
+            }
+            self.set_position(position).await?;
+
+            if finished {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::path::Interpolation;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+
+    #[tokio::test]
+    async fn camera_uses_vanilla_commands() {
+        let mut camera = World::new(MockConnection::new(ServerKind::Vanilla)).camera();
+        camera.set_follow(Some(EntityId(1))).await.unwrap();
+        camera.set_normal(None).await.unwrap();
+        camera.set_third_person(None).await.unwrap();
+        assert_eq!(
+            camera.into_inner().connection().await.sent,
+            [
+                "camera.mode.setFollow(1)\n",
+                "camera.mode.setNormal()\n",
+                "camera.mode.setThirdPerson()\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn camera_uses_raspberry_jam_commands() {
+        let mut camera = World::new(MockConnection::new(ServerKind::RaspberryJam)).camera();
+        camera.set_follow(Some(EntityId(1))).await.unwrap();
+        camera.set_normal(None).await.unwrap();
+        camera.set_third_person(None).await.unwrap();
+        camera.set_mode(CameraMode::Debug).await.unwrap();
+        camera.set_distance(2.5).await.unwrap();
+        assert_eq!(
+            camera.into_inner().connection().await.sent,
+            [
+                "camera.setFollow(1)\n",
+                "camera.setNormal()\n",
+                "camera.setThirdPerson()\n",
+                "camera.setDebug()\n",
+                "camera.setDistance(2.5)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn camera_entity_id() {
+        let connection = MockConnection::new(ServerKind::RaspberryJam).with_response("42");
+        let camera = World::new(connection).camera();
+        assert_eq!(camera.entity_id().await.unwrap(), EntityId(42));
+    }
+
+    #[tokio::test]
+    async fn camera_raspberry_jam_extensions_unsupported_on_vanilla() {
+        let mut camera = World::new(MockConnection::new(ServerKind::Vanilla)).camera();
+        assert!(matches!(
+            camera.set_distance(2.5).await,
+            Err(crate::WorldError::Unsupported {
+                server_kind: ServerKind::Vanilla
+            })
+        ));
+        assert!(camera.into_inner().connection().await.sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn play_path_rejects_invalid_frame_rates() {
+        let mut camera = World::new(MockConnection::new(ServerKind::Vanilla)).camera();
+        let path = CameraPath::new(Interpolation::Linear)
+            .with_keyframe(Duration::ZERO, Point3::origin())
+            .with_keyframe(Duration::from_secs(1), Point3::new(1.0, 0.0, 0.0));
+        let control = PlaybackControl::new();
+        for frame_rate in [0.0, -30.0, f64::NAN, f64::INFINITY, 1e300] {
+            assert!(matches!(
+                camera.play_path(&path, frame_rate, &control).await,
+                Err(crate::WorldError::InvalidFrameRate { .. })
+            ));
+        }
+        assert!(camera.into_inner().connection().await.sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn camera_mode_is_shared_and_restorable() {
+        let world = World::new(MockConnection::new(ServerKind::Vanilla));
+        let mut camera = world.camera();
+        assert_eq!(camera.mode(), None);
+        camera.set_follow(Some(EntityId(1))).await.unwrap();
+        assert_eq!(
+            world.camera().mode(),
+            Some(CameraMode::Follow(Some(EntityId(1))))
+        );
+
+        camera
+            .set_position(Point3::new(1.0, 2.0, 3.0))
+            .await
+            .unwrap();
+        world.camera().restore_mode().await.unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "camera.mode.setFollow(1)\n",
+                "camera.setPos(1,2,3)\n",
+                "camera.mode.setFollow(1)\n",
+            ]
+        );
+    }
 }
@@ -0,0 +1,338 @@
+//! Keyframed camera paths that can be played back for cinematic flyovers.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use nalgebra::{Point3, Vector3};
+use tokio::sync::watch;
+
+use crate::connection::EntityId;
+
+/// A position that the camera should be at a certain point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// The time since the start of the path.
+    pub time: Duration,
+    /// The position of the camera at that time.
+    pub position: Point3<f64>,
+}
+
+/// The method used to calculate camera positions between keyframes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Moves in a straight line between keyframes.
+    #[default]
+    Linear,
+    /// Moves along a smooth curve that passes through every keyframe.
+    CatmullRom,
+}
+
+/// A series of keyframes that the camera moves through over time.
+///
+/// # Example
+///
+/// ```
+/// # use mcpi::camera::path::{CameraPath, Interpolation};
+/// # use nalgebra::Point3;
+/// # use std::time::Duration;
+/// let path = CameraPath::new(Interpolation::Linear)
+///     .with_keyframe(Duration::ZERO, Point3::new(0.0, 10.0, 0.0))
+///     .with_keyframe(Duration::from_secs(2), Point3::new(20.0, 10.0, 0.0));
+/// assert_eq!(
+///     path.position_at(Duration::from_secs(1)),
+///     Some(Point3::new(10.0, 10.0, 0.0))
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+    interpolation: Interpolation,
+    looping: bool,
+    follow: Option<(EntityId, Vector3<f64>)>,
+}
+
+impl CameraPath {
+    /// Creates an empty path that uses the given interpolation method.
+    pub const fn new(interpolation: Interpolation) -> Self {
+        Self {
+            keyframes: Vec::new(),
+            interpolation,
+            looping: false,
+            follow: None,
+        }
+    }
+
+    /// Adds a keyframe to the path, keeping keyframes sorted by time.
+    ///
+    /// If a keyframe already exists at the given time, it is replaced.
+    pub fn add_keyframe(&mut self, time: Duration, position: Point3<f64>) {
+        let keyframe = Keyframe { time, position };
+        match self.keyframes.binary_search_by_key(&time, |k| k.time) {
+            Ok(idx) => self.keyframes[idx] = keyframe,
+            Err(idx) => self.keyframes.insert(idx, keyframe),
+        }
+    }
+
+    /// Adds a keyframe to the path. See [`Self::add_keyframe`].
+    pub fn with_keyframe(mut self, time: Duration, position: Point3<f64>) -> Self {
+        self.add_keyframe(time, position);
+        self
+    }
+
+    /// Sets whether the path starts over from the beginning once it ends.
+    pub const fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Makes the path follow an entity.
+    ///
+    /// While following, keyframe positions are relative to the entity's
+    /// current position plus the given offset.
+    pub const fn following(mut self, entity: EntityId, offset: Vector3<f64>) -> Self {
+        self.follow = Some((entity, offset));
+        self
+    }
+
+    /// Returns the path's keyframes, sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Returns the interpolation method used by the path.
+    pub const fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Returns whether the path starts over from the beginning once it ends.
+    pub const fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Returns the entity being followed and the offset from it, if any.
+    pub const fn follow(&self) -> Option<(EntityId, Vector3<f64>)> {
+        self.follow
+    }
+
+    /// Returns the time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last().map_or(Duration::ZERO, |k| k.time)
+    }
+
+    /// Calculates the position of the camera at the given time, or [`None`] if
+    /// the path has no keyframes.
+    ///
+    /// Times outside of the path are clamped to its first or last keyframe,
+    /// unless the path is looping. The result does not include the position of
+    /// a followed entity.
+    pub fn position_at(&self, time: Duration) -> Option<Point3<f64>> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+
+        let time = if self.looping && last.time > first.time {
+            let length = (last.time - first.time).as_secs_f64();
+            let since_start = time.saturating_sub(first.time).as_secs_f64();
+            first.time + Duration::from_secs_f64(since_start % length)
+        } else {
+            time
+        };
+
+        if time <= first.time {
+            return Some(first.position);
+        }
+        if time >= last.time {
+            return Some(last.position);
+        }
+
+        // Index of the first keyframe after `time`; always in 1..len.
+        let next = self.keyframes.partition_point(|k| k.time <= time);
+        let start = &self.keyframes[next - 1];
+        let end = &self.keyframes[next];
+        let t = (time - start.time).as_secs_f64() / (end.time - start.time).as_secs_f64();
+
+        Some(match self.interpolation {
+            Interpolation::Linear => start.position + (end.position - start.position) * t,
+            Interpolation::CatmullRom => {
+                let before = next
+                    .checked_sub(2)
+                    .map_or(start.position, |i| self.keyframes[i].position);
+                let after = self
+                    .keyframes
+                    .get(next + 1)
+                    .map_or(end.position, |k| k.position);
+                catmull_rom(before, start.position, end.position, after, t)
+            }
+        })
+    }
+}
+
+/// Evaluates a uniform Catmull-Rom spline segment between `p1` and `p2`.
+fn catmull_rom(
+    p0: Point3<f64>,
+    p1: Point3<f64>,
+    p2: Point3<f64>,
+    p3: Point3<f64>,
+    t: f64,
+) -> Point3<f64> {
+    let (p0, p1, p2, p3) = (p0.coords, p1.coords, p2.coords, p3.coords);
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let coords = (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5;
+    Point3::from(coords)
+}
+
+/// The playback state of a [`CameraPath`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaybackState {
+    /// The camera is moving along the path.
+    #[default]
+    Playing,
+    /// The camera is holding its position until playback is resumed.
+    Paused,
+    /// Playback has ended early.
+    Stopped,
+}
+
+/// Controls the playback of a [`CameraPath`] from another task.
+///
+/// Cloned handles control the same playback.
+#[derive(Debug, Clone)]
+pub struct PlaybackControl {
+    state: Arc<watch::Sender<PlaybackState>>,
+}
+
+impl Default for PlaybackControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackControl {
+    /// Creates a new handle in the [`PlaybackState::Playing`] state.
+    pub fn new() -> Self {
+        let (state, _) = watch::channel(PlaybackState::default());
+        Self {
+            state: Arc::new(state),
+        }
+    }
+
+    /// Returns the current playback state.
+    pub fn state(&self) -> PlaybackState {
+        *self.state.borrow()
+    }
+
+    /// Pauses playback. The path will continue from the same point when it
+    /// is resumed.
+    pub fn pause(&self) {
+        self.set_state(PlaybackState::Paused);
+    }
+
+    /// Resumes paused playback.
+    pub fn resume(&self) {
+        self.set_state(PlaybackState::Playing);
+    }
+
+    /// Stops playback. Stopped playback cannot be resumed.
+    pub fn stop(&self) {
+        self.set_state(PlaybackState::Stopped);
+    }
+
+    fn set_state(&self, state: PlaybackState) {
+        self.state.send_if_modified(|current| {
+            if *current == state || *current == PlaybackState::Stopped {
+                false
+            } else {
+                *current = state;
+                true
+            }
+        });
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<PlaybackState> {
+        self.state.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(interpolation: Interpolation) -> CameraPath {
+        CameraPath::new(interpolation)
+            .with_keyframe(Duration::from_secs(2), Point3::new(10.0, 0.0, 10.0))
+            .with_keyframe(Duration::ZERO, Point3::new(0.0, 0.0, 0.0))
+            .with_keyframe(Duration::from_secs(1), Point3::new(10.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn camera_path_empty_has_no_position() {
+        let path = CameraPath::new(Interpolation::Linear);
+        assert_eq!(path.position_at(Duration::ZERO), None);
+    }
+
+    #[test]
+    fn camera_path_sorts_keyframes() {
+        let path = path(Interpolation::Linear);
+        let times = path.keyframes().iter().map(|k| k.time).collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_secs(2)
+            ]
+        );
+        assert_eq!(path.duration(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn camera_path_linear() {
+        let path = path(Interpolation::Linear);
+        assert_eq!(
+            path.position_at(Duration::from_millis(500)),
+            Some(Point3::new(5.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            path.position_at(Duration::from_millis(1500)),
+            Some(Point3::new(10.0, 0.0, 5.0))
+        );
+        assert_eq!(
+            path.position_at(Duration::from_secs(5)),
+            Some(Point3::new(10.0, 0.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn camera_path_catmull_rom_passes_through_keyframes() {
+        let path = path(Interpolation::CatmullRom);
+        for keyframe in path.keyframes() {
+            assert_eq!(path.position_at(keyframe.time), Some(keyframe.position));
+        }
+        // The curve bends outwards instead of cutting the corner.
+        let midpoint = path.position_at(Duration::from_millis(500)).unwrap();
+        assert!(midpoint.z < 0.0);
+    }
+
+    #[test]
+    fn camera_path_looping_wraps() {
+        let path = path(Interpolation::Linear).looping(true);
+        assert_eq!(
+            path.position_at(Duration::from_millis(2500)),
+            Some(Point3::new(5.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn playback_control_cannot_resume_after_stop() {
+        let control = PlaybackControl::new();
+        control.pause();
+        assert_eq!(control.state(), PlaybackState::Paused);
+        control.stop();
+        control.resume();
+        assert_eq!(control.state(), PlaybackState::Stopped);
+    }
+}
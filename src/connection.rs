@@ -30,6 +30,11 @@ use crate::util::{Cp437String, CHAR_TO_CP437};
 ///
 /// Vanilla blocks are available as associated constants.
 ///
+/// With the `serde` feature, human-readable formats such as JSON store vanilla
+/// tiles by name (such as `"diamond_block"`) and other tiles by their ID as a
+/// string; names and IDs can both be deserialized. Other formats always store
+/// the numeric ID.
+///
 /// See also: [Minecraft: Pi Edition Complete Block List](https://mcpirevival.miraheze.org/wiki/Minecraft:_Pi_Edition_Complete_Block_List)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, FromStr)]
 pub struct Tile(pub u8);
@@ -208,6 +213,7 @@ impl Display for TileDisplay {
             Tile::CLAY => write!(f, "Clay"),
             Tile::SUGARCANE => write!(f, "Sugarcane"),
             Tile::FENCE => write!(f, "Fence"),
+            Tile::NETHERRACK => write!(f, "Netherrack"),
             Tile::NETHER_BRICKS => write!(f, "Nether Bricks"),
             Tile::GLOWSTONE => write!(f, "Glowstone"),
             Tile::INVISIBLE_BEDROCK => write!(f, "Invisible Bedrock"),
@@ -236,6 +242,67 @@ impl Display for TileDisplay {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tile {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_u8(self.0);
+        }
+        match crate::block::name::tile_name(*self) {
+            Some(name) => serializer.serialize_str(name),
+            None => serializer.collect_str(&self.0),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected, Visitor};
+
+        struct TileVisitor;
+
+        impl Visitor<'_> for TileVisitor {
+            type Value = Tile;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a tile name or ID")
+            }
+
+            fn visit_u8<E: Error>(self, v: u8) -> Result<Tile, E> {
+                Ok(Tile(v))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Tile, E> {
+                u8::try_from(v)
+                    .map(Tile)
+                    .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Tile, E> {
+                u8::try_from(v)
+                    .map(Tile)
+                    .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Tile, E> {
+                match crate::block::name::parse_tile(v).map_err(E::custom)? {
+                    (tile, None) => Ok(tile),
+                    // Names like `red_wool` also set the block's data.
+                    (_, Some(_)) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+                }
+            }
+        }
+
+        // Only self-describing formats can hold either a name or an ID.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TileVisitor)
+        } else {
+            deserializer.deserialize_u8(TileVisitor)
+        }
+    }
+}
+
 /// Extra data that can be attached to a block, specific to that block type.
 ///
 /// For many blocks, this data is used to represent the block's state, such as
@@ -250,6 +317,7 @@ impl Display for TileDisplay {
 #[derive(
     Debug, Clone, Default, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, AsRef, Display, FromStr,
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[as_ref(forward)]
 pub struct TileData(pub u8);
 
@@ -564,6 +632,7 @@ impl MCPIExtrasKey<'_> {
     pub const UP: Self = Self(ApiStr("UP"));
 
     pub const LSHIFT: Self = Self(ApiStr("LSHIFT"));
+    pub const SPACE: Self = Self(ApiStr("SPACE"));
 }
 
 /// The color of a sheep.
@@ -593,6 +662,48 @@ impl SheepColor {
     pub const BLACK: Self = Self(15);
 }
 
+/// Sheep and wool share the same palette, so a wool block's [`TileData`] is
+/// the matching sheep color.
+impl TryFrom<TileData> for SheepColor {
+    type Error = InvalidWoolColorError;
+
+    fn try_from(data: TileData) -> Result<Self, Self::Error> {
+        match data.0 {
+            0..=15 => Ok(Self(data.0.into())),
+            data => InvalidWoolColorSnafu { data }.fail(),
+        }
+    }
+}
+
+/// An error that occurs when converting a [`TileData`] that is not a wool
+/// color.
+#[derive(Debug, Snafu)]
+#[snafu(display("Tile data {data} is not a wool color"))]
+pub struct InvalidWoolColorError {
+    data: u8,
+}
+
+impl TryFrom<SheepColor> for TileData {
+    type Error = InvalidSheepColorError;
+
+    /// Converts a sheep color into the [`TileData`] of the matching wool
+    /// block.
+    fn try_from(color: SheepColor) -> Result<Self, Self::Error> {
+        match color.0 {
+            0..=15 => Ok(Self(color.0 as u8)),
+            color => InvalidSheepColorSnafu { color }.fail(),
+        }
+    }
+}
+
+/// An error that occurs when converting a [`SheepColor`] that does not have a
+/// matching wool color.
+#[derive(Debug, Snafu)]
+#[snafu(display("Sheep color {color} has no matching wool color"))]
+pub struct InvalidSheepColorError {
+    color: i32,
+}
+
 /// An entity type supported by the MCPI Addons API extension.
 ///
 /// See also: [MCPI Addons Reference Implementation](https://github.com/Bigjango13/MCPI-Addons/blob/05027ab7277d51c0dcdd93b58d2ddb66dfea92df/mcpi_addons/entity.py#L56-L100)
@@ -770,13 +881,66 @@ impl RaspberryJamParticle<'_> {
 }
 
 /// A world dimension that can be used with the Raspberry Jam API extension.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, AsRef, Display, FromStr)]
-pub struct Dimension(pub i32);
+///
+/// Dimensions are displayed and parsed as their numeric IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dimension {
+    Overworld,
+    Nether,
+    End,
+    /// A dimension ID that is not known by this library, such as one added by
+    /// a mod. Parsed IDs of the other variants never end up here.
+    Unknown(i32),
+}
 
 impl Dimension {
-    pub const OVERWORLD: Self = Self(0);
-    pub const NETHER: Self = Self(-1);
-    pub const END: Self = Self(1);
+    #[deprecated(note = "use `Dimension::Overworld` instead")]
+    pub const OVERWORLD: Self = Self::Overworld;
+    #[deprecated(note = "use `Dimension::Nether` instead")]
+    pub const NETHER: Self = Self::Nether;
+    #[deprecated(note = "use `Dimension::End` instead")]
+    pub const END: Self = Self::End;
+
+    /// Returns the numeric ID of the dimension.
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::Overworld => 0,
+            Self::Nether => -1,
+            Self::End => 1,
+            Self::Unknown(id) => id,
+        }
+    }
+}
+
+impl From<i32> for Dimension {
+    fn from(id: i32) -> Self {
+        match id {
+            0 => Self::Overworld,
+            -1 => Self::Nether,
+            1 => Self::End,
+            id => Self::Unknown(id),
+        }
+    }
+}
+
+impl From<Dimension> for i32 {
+    fn from(dimension: Dimension) -> Self {
+        dimension.id()
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for Dimension {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse::<i32>()?.into())
+    }
 }
 
 /// A player-related setting that can be updated using the API.
@@ -826,6 +990,7 @@ impl WorldSettingKey<'_> {
 
 /// The identifier of an entity in the game world.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, AsRef, Display, FromStr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityId(pub i32);
 
 // MARK: Commands
@@ -997,15 +1162,60 @@ pub enum ConnectionError {
     QueueFull { backtrace: Backtrace },
 }
 
+/// The kind of game server on the other end of a connection, which determines
+/// the API extensions that are available.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServerKind {
+    /// The vanilla Minecraft: Pi Edition API, without any extensions.
+    #[default]
+    Vanilla,
+    /// A Bukkit server with the [Raspberry Juice](https://dev.bukkit.org/projects/raspberryjuice)
+    /// plugin.
+    RaspberryJuice,
+    /// A Minecraft: Java Edition game with the [Raspberry Jam](https://github.com/arpruss/raspberryjammod)
+    /// mod, which also supports most Raspberry Juice extensions.
+    RaspberryJam,
+    /// A Minecraft Pi: Reborn game with the [MCPI Addons](https://github.com/Bigjango13/MCPI-Addons)
+    /// mod.
+    McpiAddons,
+}
+
+impl ServerKind {
+    /// Returns whether the server supports the Raspberry Juice API extensions.
+    pub const fn supports_raspberry_juice(self) -> bool {
+        matches!(self, Self::RaspberryJuice | Self::RaspberryJam)
+    }
+
+    /// Returns whether the server supports the Raspberry Jam API extensions.
+    pub const fn supports_raspberry_jam(self) -> bool {
+        matches!(self, Self::RaspberryJam)
+    }
+
+    /// Returns whether the server supports the MCPI Addons API extensions.
+    pub const fn supports_mcpi_addons(self) -> bool {
+        matches!(self, Self::McpiAddons)
+    }
+}
+
 /// Options that can be set to change the behavior of the connection to the
 /// game.
+///
+/// With the `serde` feature, missing fields take their default values and
+/// durations use a human-friendly format such as `"1s"` or `"250ms"`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ConnectOptions {
     /// The amount of time to wait for a response from the server before giving
     /// up. Setting this to a higher value may slow performance,
     /// but has a smaller chance of causing a timeout error.
     ///
     /// Defaults to 1 second.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub response_timeout: Option<Duration>,
     /// Whether to always wait for a response from the server.
     ///
@@ -1019,6 +1229,13 @@ pub struct ConnectOptions {
     /// commands that do not require a response will need to wait
     /// [`response_timeout`] seconds before continuing.
     pub always_wait_for_response: bool,
+    /// The kind of server being connected to.
+    ///
+    /// This is used to pick between API extensions when a feature is
+    /// implemented differently by different servers.
+    ///
+    /// Defaults to [`ServerKind::Vanilla`].
+    pub server_kind: ServerKind,
 }
 
 impl Default for ConnectOptions {
@@ -1026,6 +1243,7 @@ impl Default for ConnectOptions {
         Self {
             response_timeout: Some(Duration::from_secs(1)),
             always_wait_for_response: false,
+            server_kind: ServerKind::Vanilla,
         }
     }
 }
@@ -1041,6 +1259,13 @@ pub trait Protocol: Debug {
 
     /// Flushes the connection and disconnects.
     fn close(&mut self) -> impl Future<Output = Result<(), ConnectionError>> + Send;
+
+    /// Returns the kind of server on the other end of the connection.
+    ///
+    /// Defaults to [`ServerKind::Vanilla`].
+    fn server_kind(&self) -> ServerKind {
+        ServerKind::Vanilla
+    }
 }
 
 /// A connection to a game server using the Minecraft: Pi Edition API protocol.
@@ -1173,8 +1398,15 @@ impl Protocol for ServerConnection {
         self.socket.shutdown().await?;
         Ok(())
     }
+
+    fn server_kind(&self) -> ServerKind {
+        self.options.server_kind
+    }
 }
 
+#[cfg(test)]
+pub(crate) mod testing;
+
 // MARK: Tests
 
 #[cfg(test)]
@@ -1207,6 +1439,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sheep_color_matches_wool() {
+        assert_eq!(
+            SheepColor::try_from(TileData::RED).unwrap(),
+            SheepColor::RED
+        );
+        assert!(SheepColor::try_from(TileData(16)).is_err());
+        assert_eq!(
+            TileData::try_from(SheepColor::LIME).unwrap(),
+            TileData::LIME
+        );
+        assert!(TileData::try_from(SheepColor(16)).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn dimension_ids() {
+        assert_eq!(Dimension::NETHER, Dimension::Nether);
+        assert_eq!(Dimension::Nether.to_string(), "-1");
+        assert_eq!("1".parse::<Dimension>().unwrap(), Dimension::End);
+        assert_eq!(Dimension::from(7), Dimension::Unknown(7));
+        assert_eq!(i32::from(Dimension::Unknown(7)), 7);
+        assert_eq!("0".parse::<Dimension>().unwrap(), Dimension::Overworld);
+        assert_ne!(Dimension::Unknown(7), Dimension::Unknown(8));
+    }
+
     #[test]
     fn mcpi_extras_entity_new_arrow() {
         let entity = MCPIExtrasEntityVariant::new_arrow(true);
@@ -1266,4 +1524,48 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tile_serde() {
+        use serde_json::json;
+
+        assert_eq!(serde_json::to_value(Tile::WOOL).unwrap(), json!("wool"));
+        assert_eq!(serde_json::to_value(Tile(200)).unwrap(), json!("200"));
+        assert_eq!(
+            serde_json::from_value::<Tile>(json!("200")).unwrap(),
+            Tile(200)
+        );
+        assert_eq!(
+            serde_json::from_value::<Tile>(json!(35)).unwrap(),
+            Tile::WOOL
+        );
+        assert!(serde_json::from_value::<Tile>(json!("red_wool")).is_err());
+        assert!(serde_json::from_value::<Tile>(json!(256)).is_err());
+
+        for tile in [Tile::WOOL, Tile(200)] {
+            let bytes = bincode::serialize(&tile).unwrap();
+            assert_eq!(bytes, [tile.0]);
+            assert_eq!(bincode::deserialize::<Tile>(&bytes).unwrap(), tile);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn connect_options_serde() {
+        let options = serde_json::from_str::<ConnectOptions>(
+            r#"{ "response_timeout": "250ms", "server_kind": "RaspberryJam" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            options,
+            ConnectOptions {
+                response_timeout: Some(Duration::from_millis(250)),
+                server_kind: ServerKind::RaspberryJam,
+                ..Default::default()
+            }
+        );
+        let value = serde_json::to_value(ConnectOptions::default()).unwrap();
+        assert_eq!(value["response_timeout"], "1s");
+    }
 }
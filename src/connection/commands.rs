@@ -391,22 +391,22 @@ mod test {
         }
     }
 
-    // #[test]
-    // fn raspberry_jam_camera_apis_have_no_mode() {
-    //     let command = CameraModeSetNormal { target: None };
-    //     assert_eq!(command.to_command_bytes(), b"camera.mode.setNormal()\n");
-    //     let command = CameraSetNormal { target: None };
-    //     assert_eq!(command.to_command_bytes(), b"camera.setNormal()\n");
-    //     let command = CameraModeSetThirdPerson { target: None };
-    //     assert_eq!(
-    //         command.to_command_bytes(),
-    //         b"camera.mode.setThirdPerson()\n"
-    //     );
-    //     let command = CameraSetThirdPerson { target: None };
-    //     assert_eq!(command.to_command_bytes(), b"camera.setThirdPerson()\n");
-    //     let command = CameraModeSetFollow { target: None };
-    //     assert_eq!(command.to_command_bytes(), b"camera.mode.setFollow()\n");
-    //     let command = CameraSetFollow { target: None };
-    //     assert_eq!(command.to_command_bytes(), b"camera.setFollow()\n");
-    // }
+    #[test]
+    fn raspberry_jam_camera_apis_have_no_mode() {
+        let command = CameraModeSetNormal { target: None };
+        assert_eq!(command.to_command_bytes(), b"camera.mode.setNormal()\n");
+        let command = raspberry_jam::CameraSetNormal { target: None };
+        assert_eq!(command.to_command_bytes(), b"camera.setNormal()\n");
+        let command = CameraModeSetThirdPerson { target: None };
+        assert_eq!(
+            command.to_command_bytes(),
+            b"camera.mode.setThirdPerson()\n"
+        );
+        let command = raspberry_jam::CameraSetThirdPerson { target: None };
+        assert_eq!(command.to_command_bytes(), b"camera.setThirdPerson()\n");
+        let command = CameraModeSetFollow { target: None };
+        assert_eq!(command.to_command_bytes(), b"camera.mode.setFollow()\n");
+        let command = raspberry_jam::CameraSetFollow { target: None };
+        assert_eq!(command.to_command_bytes(), b"camera.setFollow()\n");
+    }
 }
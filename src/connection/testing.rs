@@ -0,0 +1,54 @@
+//! Utilities for testing code that communicates with a game server.
+
+use std::collections::VecDeque;
+
+use super::commands::SerializableCommand;
+use super::{ConnectionError, Protocol, ServerKind};
+
+/// A fake connection that records the commands sent to it and replies with
+/// pre-recorded responses.
+#[derive(Debug, Default)]
+pub(crate) struct MockConnection {
+    pub server_kind: ServerKind,
+    /// Every command that has been sent, in order.
+    pub sent: Vec<String>,
+    /// Responses to commands that expect one, in order.
+    pub responses: VecDeque<String>,
+}
+
+impl MockConnection {
+    pub(crate) fn new(server_kind: ServerKind) -> Self {
+        Self {
+            server_kind,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_response(mut self, response: &str) -> Self {
+        self.responses.push_back(response.to_string());
+        self
+    }
+}
+
+impl Protocol for MockConnection {
+    async fn send<T: SerializableCommand>(
+        &mut self,
+        command: T,
+    ) -> Result<String, ConnectionError> {
+        self.sent
+            .push(String::from_utf8_lossy(&command.to_command_bytes()).into_owned());
+        if T::HAS_RESPONSE {
+            Ok(self.responses.pop_front().unwrap_or_default())
+        } else {
+            Ok(String::new())
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), ConnectionError> {
+        Ok(())
+    }
+
+    fn server_kind(&self) -> ServerKind {
+        self.server_kind
+    }
+}
@@ -1,11 +1,18 @@
 use std::future::Future;
+use std::time::{Duration, Instant};
 
-use nalgebra::Point3;
+use futures_core::Stream;
+use itertools::Itertools;
+use nalgebra::{distance, Point3, Vector3};
+use snafu::OptionExt;
 
 use crate::connection::commands::*;
-use crate::connection::{EntityId, PlayerSettingKey, Protocol};
+use crate::connection::{
+    ConnectionError, Dimension, EntityId, PlayerSettingKey, Protocol, ServerKind, SheepColor,
+};
+use crate::input::InputController;
 use crate::util::parse_point;
-use crate::{Result, World};
+use crate::{pos_to_tile, NotEnoughPartsSnafu, Result, World, WorldError};
 
 pub trait Entity {
     /// Returns the entity's ID, or None if this is the client player.
@@ -21,6 +28,146 @@ pub trait Entity {
     fn get_tile(&self) -> impl Future<Output = Result<Point3<i16>>>;
     /// Sets the 3D coordinates of the entity as an integer Point.
     fn set_tile(&mut self, tile: Point3<i16>) -> impl Future<Output = Result>;
+
+    /// Creates a stream of movement samples, taken by polling the entity's
+    /// position at the given interval. If the connection's event queue is
+    /// full, polls will not be sent.
+    ///
+    /// This is shorthand for [`Self::movement_with_threshold`] with a
+    /// threshold of `0.0`, meaning that every poll produces a sample.
+    fn movement(&self, interval: Duration) -> impl Stream<Item = Result<MovementSample>> + '_
+    where
+        Self: Sized,
+    {
+        self.movement_with_threshold(interval, 0.0)
+    }
+
+    /// Creates a stream of movement samples, taken by polling the entity's
+    /// position at the given interval.
+    ///
+    /// Polls where the entity has moved less than `threshold` blocks since the
+    /// last sample are skipped, which can be used to filter out jitter.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval at which to poll for the entity's position.
+    /// * `threshold` - The minimum distance (in blocks) the entity must move
+    ///   for a new sample to be produced.
+    fn movement_with_threshold(
+        &self,
+        interval: Duration,
+        threshold: f64,
+    ) -> impl Stream<Item = Result<MovementSample>> + '_
+    where
+        Self: Sized,
+    {
+        async_stream::stream! {
+            let mut tracker = MovementTracker::with_threshold(threshold);
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                let position = match self.get_position().await {
+                    Ok(position) => position,
+                    Err(WorldError::Connection { source: ConnectionError::QueueFull { .. } }) => {
+                        continue;
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                };
+                if let Some(sample) = tracker.update(position, Instant::now()) {
+                    yield Ok(sample);
+                }
+            }
+        }
+    }
+}
+
+/// Entities that can be moved between dimensions using the Raspberry Jam API
+/// extension.
+pub trait DimensionEntity: Entity {
+    /// Moves the entity to another dimension.
+    ///
+    /// Raspberry Jam server only!
+    fn set_dimension(&mut self, dimension: Dimension) -> impl Future<Output = Result>;
+}
+
+/// A snapshot of an entity's movement.
+///
+/// Samples are produced by [`Entity::movement`], or by feeding positions to a
+/// [`MovementTracker`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementSample {
+    /// The position of the entity when the sample was taken.
+    pub position: Point3<f64>,
+    /// The tile the entity was in when the sample was taken.
+    pub tile: Point3<i16>,
+    /// The estimated velocity of the entity since the previous sample, in
+    /// blocks per second.
+    pub velocity: Vector3<f64>,
+    /// Whether the entity has moved to a different tile since the previous
+    /// sample.
+    pub on_tile_change: bool,
+}
+
+/// Converts a series of entity positions into [`MovementSample`]s.
+#[derive(Debug, Clone, Default)]
+pub struct MovementTracker {
+    threshold: f64,
+    last: Option<(Point3<f64>, Instant)>,
+}
+
+impl MovementTracker {
+    /// Creates a tracker that produces a sample for every position.
+    pub const fn new() -> Self {
+        Self::with_threshold(0.0)
+    }
+
+    /// Creates a tracker that skips positions less than `threshold` blocks away
+    /// from the last sample.
+    pub const fn with_threshold(threshold: f64) -> Self {
+        Self {
+            threshold,
+            last: None,
+        }
+    }
+
+    /// Records the entity's position at the given instant, returning a new
+    /// sample unless the entity has moved less than the tracker's threshold.
+    ///
+    /// The first sample always has a velocity of zero.
+    pub fn update(&mut self, position: Point3<f64>, now: Instant) -> Option<MovementSample> {
+        let tile = pos_to_tile(&position);
+        let Some((last_position, last_time)) = self.last else {
+            self.last = Some((position, now));
+            return Some(MovementSample {
+                position,
+                tile,
+                velocity: Vector3::zeros(),
+                on_tile_change: false,
+            });
+        };
+
+        if distance(&position, &last_position) < self.threshold {
+            return None;
+        }
+
+        let elapsed = now.saturating_duration_since(last_time).as_secs_f64();
+        let velocity = if elapsed > 0.0 {
+            (position - last_position) / elapsed
+        } else {
+            Vector3::zeros()
+        };
+
+        self.last = Some((position, now));
+        Some(MovementSample {
+            position,
+            tile,
+            velocity,
+            on_tile_change: tile != pos_to_tile(&last_position),
+        })
+    }
 }
 
 /// A player's entity ID with a connection to their game.
@@ -54,58 +201,211 @@ impl<T: Protocol> Player<T> {
     pub const fn id(&self) -> EntityId {
         self.id
     }
-}
 
-impl<T: Protocol> Entity for Player<T> {
-    fn entity_id(&self) -> Option<EntityId> {
-        Some(self.id)
+    /// Gets the player's name.
+    ///
+    /// Raspberry Juice or Raspberry Jam server only!
+    pub async fn name(&self) -> Result<String> {
+        let server_kind = self.world.server_kind().await;
+        if server_kind.supports_raspberry_jam() {
+            let (name, _) = self.name_and_uuid().await?;
+            return Ok(name);
+        }
+        self.world
+            .require(ServerKind::supports_raspberry_juice)
+            .await?;
+        Ok(self
+            .world
+            .send_command(raspberry_juice::EntityGetName { entity_id: self.id })
+            .await?)
     }
 
-    async fn get_position(&self) -> Result<Point3<f64>> {
-        let pos = self
+    /// Gets the player's UUID.
+    ///
+    /// Raspberry Jam server only!
+    pub async fn uuid(&self) -> Result<String> {
+        self.world.require_raspberry_jam().await?;
+        let (_, uuid) = self.name_and_uuid().await?;
+        Ok(uuid)
+    }
+
+    async fn name_and_uuid(&self) -> Result<(String, String)> {
+        let response = self
             .world
-            .send_command(EntityGetPos { target: self.id })
+            .send_command(raspberry_jam::EntityGetNameAndUUID { entity_id: self.id })
             .await?;
-        let vec = parse_point(&pos)?;
-        Ok(vec)
+        let (name, uuid) = response.rsplit_once(',').context(NotEnoughPartsSnafu)?;
+        Ok((name.to_owned(), uuid.to_owned()))
     }
+}
 
-    async fn set_position(&mut self, position: Point3<f64>) -> Result {
+/// An entity ID with a connection to its game.
+///
+/// This struct is used to interact with any kind of entity in the game world,
+/// such as mobs spawned using the MCPI Addons API extension.
+#[derive(Debug)]
+pub struct EntityRef<T: Protocol> {
+    world: World<T>,
+    id: EntityId,
+}
+
+impl<T: Protocol> Clone for EntityRef<T> {
+    fn clone(&self) -> Self {
+        Self {
+            world: self.world.clone(),
+            id: self.id,
+        }
+    }
+}
+
+impl<T: Protocol> EntityRef<T> {
+    pub const fn new(world: World<T>, id: EntityId) -> Self {
+        Self { world, id }
+    }
+
+    pub fn into_inner(self) -> World<T> {
         self.world
-            .send_command(EntitySetPos {
-                target: self.id,
-                coords: position,
-            })
-            .await?;
-        Ok(())
     }
 
-    async fn get_tile(&self) -> Result<Point3<i16>> {
-        let tile = self
-            .world
-            .send_command(EntityGetTile { target: self.id })
+    /// Returns the ID of the entity.
+    pub const fn id(&self) -> EntityId {
+        self.id
+    }
+
+    /// Sets the age of a mob, such as turning it into a baby.
+    ///
+    /// MCPI Addons server only!
+    pub async fn set_age(&mut self, age: Age) -> Result {
+        self.world.require_mcpi_addons().await?;
+        self.world
+            .send_command(mcpi_addons::CustomEntitySetAge {
+                entity_id: self.id,
+                age: age.into(),
+            })
             .await?;
-        let vec = parse_point(&tile)?;
-        Ok(vec)
+        Ok(())
     }
 
-    async fn set_tile(&mut self, tile: Point3<i16>) -> Result {
+    /// Sets the color of a sheep's wool.
+    ///
+    /// MCPI Addons server only!
+    pub async fn set_sheep_color(&mut self, color: SheepColor) -> Result {
+        self.world.require_mcpi_addons().await?;
         self.world
-            .send_command(EntitySetTile {
-                target: self.id,
-                coords: tile,
+            .send_command(mcpi_addons::CustomEntitySetSheepColor {
+                entity_id: self.id,
+                color,
             })
             .await?;
         Ok(())
     }
 }
 
+/// Implements [`Entity`] for types that hold a `world` and the `id` of an
+/// entity, using the commands that work on any entity.
+macro_rules! impl_entity {
+    ($($ty:ident),*) => {$(
+        impl<T: Protocol> Entity for $ty<T> {
+            fn entity_id(&self) -> Option<EntityId> {
+                Some(self.id)
+            }
+
+            async fn get_position(&self) -> Result<Point3<f64>> {
+                let pos = self
+                    .world
+                    .send_command(EntityGetPos { target: self.id })
+                    .await?;
+                let vec = parse_point(&pos)?;
+                Ok(vec)
+            }
+
+            async fn set_position(&mut self, position: Point3<f64>) -> Result {
+                self.world
+                    .send_command(EntitySetPos {
+                        target: self.id,
+                        coords: position,
+                    })
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_tile(&self) -> Result<Point3<i16>> {
+                let tile = self
+                    .world
+                    .send_command(EntityGetTile { target: self.id })
+                    .await?;
+                let vec = parse_point(&tile)?;
+                Ok(vec)
+            }
+
+            async fn set_tile(&mut self, tile: Point3<i16>) -> Result {
+                self.world
+                    .send_command(EntitySetTile {
+                        target: self.id,
+                        coords: tile,
+                    })
+                    .await?;
+                Ok(())
+            }
+
+        }
+
+        impl<T: Protocol> DimensionEntity for $ty<T> {
+            async fn set_dimension(&mut self, dimension: Dimension) -> Result {
+                self.world.require_raspberry_jam().await?;
+                self.world
+                    .send_command(raspberry_jam::EntitySetDimension {
+                        entity_id: self.id,
+                        dimension,
+                    })
+                    .await?;
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_entity!(Player, EntityRef);
+
+/// The age of a mob, which controls whether it is a baby.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Age {
+    /// A newborn baby, which grows up after 20 minutes.
+    Baby,
+    /// A fully grown adult.
+    Adult,
+    /// A specific age in game ticks. Negative ages are babies that grow up
+    /// once their age reaches zero.
+    Ticks(i32),
+}
+
+impl Age {
+    /// The age of a newborn baby, in game ticks.
+    pub const BABY_TICKS: i32 = -24000;
+}
+
+impl From<Age> for i32 {
+    fn from(age: Age) -> Self {
+        match age {
+            Age::Baby => Age::BABY_TICKS,
+            Age::Adult => 0,
+            Age::Ticks(ticks) => ticks,
+        }
+    }
+}
+
 impl EntityId {
     /// Creates a [`Player`] instance from this entity ID, allowing interaction
     /// with the player.
     pub const fn into_player<T: Protocol>(self, world: World<T>) -> Player<T> {
         Player::new(world, self)
     }
+
+    /// Creates an [`EntityRef`] instance from this entity ID, allowing
+    /// interaction with the entity.
+    pub const fn into_entity<T: Protocol>(self, world: World<T>) -> EntityRef<T> {
+        EntityRef::new(world, self)
+    }
 }
 
 #[derive(Debug)]
@@ -149,6 +449,145 @@ impl<T: Protocol> ClientPlayer<T> {
     pub async fn set_autojump(&mut self, enabled: bool) -> Result {
         self.set(PlayerSettingKey::AUTOJUMP, enabled).await
     }
+
+    /// Gets the item in the host player's currently selected inventory slot.
+    ///
+    /// MCPI Addons server only!
+    pub async fn held_item(&self) -> Result<ItemStack> {
+        self.world.require_mcpi_addons().await?;
+        let item = self
+            .world
+            .send_command(mcpi_addons::CustomInventoryGetSlot {})
+            .await?;
+        let [id, auxiliary, count] = item
+            .split('|')
+            .collect_array()
+            .context(NotEnoughPartsSnafu)?;
+        Ok(ItemStack {
+            id: id.parse()?,
+            auxiliary: auxiliary.parse()?,
+            count: count.parse()?,
+        })
+    }
+
+    /// Adds items to the host player's inventory.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The ID of the item or tile to give.
+    /// * `auxiliary` - The item's auxiliary value, such as the [`TileData`] of
+    ///   a tile.
+    /// * `count` - The number of items to give.
+    ///
+    /// MCPI Addons server only!
+    ///
+    /// [`TileData`]: crate::connection::TileData
+    pub async fn give(&mut self, item: i32, auxiliary: i32, count: i32) -> Result {
+        self.world.require_mcpi_addons().await?;
+        self.world
+            .send_command(mcpi_addons::CustomInventoryGive {
+                id: Some(item),
+                auxillary: Some(auxiliary),
+                count: Some(count),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Gets the host player's health, in half-hearts.
+    ///
+    /// MCPI Addons server only!
+    pub async fn health(&self) -> Result<i32> {
+        self.world.require_mcpi_addons().await?;
+        let health = self
+            .world
+            .send_command(mcpi_addons::CustomPlayerGetHealth {})
+            .await?;
+        Ok(health.parse()?)
+    }
+
+    /// Sets the host player's health, in half-hearts.
+    ///
+    /// MCPI Addons server only!
+    pub async fn set_health(&mut self, health: i32) -> Result {
+        self.world.require_mcpi_addons().await?;
+        self.world
+            .send_command(mcpi_addons::CustomPlayerSetHealth { health })
+            .await?;
+        Ok(())
+    }
+
+    /// Gets the game mode of the host player.
+    ///
+    /// MCPI Addons server only!
+    pub async fn gamemode(&self) -> Result<Gamemode> {
+        self.world.require_mcpi_addons().await?;
+        let gamemode = self
+            .world
+            .send_command(mcpi_addons::CustomPlayerGetGamemode {})
+            .await?;
+        Ok(Gamemode::from(gamemode.parse::<i32>()?))
+    }
+}
+
+impl<T: Protocol + Send + 'static> ClientPlayer<T> {
+    /// Returns a controller that can simulate keyboard input for the host
+    /// player.
+    ///
+    /// MCPI Addons server only!
+    pub fn input(&self) -> InputController<T> {
+        InputController::new(self.world.clone())
+    }
+}
+
+/// A stack of items in a player's inventory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemStack {
+    /// The ID of the item or tile.
+    pub id: i32,
+    /// The item's auxiliary value, such as the [`TileData`] of a tile.
+    ///
+    /// [`TileData`]: crate::connection::TileData
+    pub auxiliary: i32,
+    /// The number of items in the stack.
+    pub count: i32,
+}
+
+/// The game mode of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gamemode {
+    Survival,
+    Creative,
+    /// A game mode ID that is not known by this library. [`From<i32>`] only
+    /// uses this for IDs that have no other variant.
+    Unknown(i32),
+}
+
+impl Gamemode {
+    /// Returns the numeric ID of the game mode.
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::Survival => 0,
+            Self::Creative => 1,
+            Self::Unknown(id) => id,
+        }
+    }
+}
+
+impl From<i32> for Gamemode {
+    fn from(id: i32) -> Self {
+        match id {
+            0 => Self::Survival,
+            1 => Self::Creative,
+            id => Self::Unknown(id),
+        }
+    }
+}
+
+impl From<Gamemode> for i32 {
+    fn from(gamemode: Gamemode) -> Self {
+        gamemode.id()
+    }
 }
 
 impl<T: Protocol> Entity for ClientPlayer<T> {
@@ -182,3 +621,183 @@ impl<T: Protocol> Entity for ClientPlayer<T> {
         Ok(())
     }
 }
+
+impl<T: Protocol> DimensionEntity for ClientPlayer<T> {
+    async fn set_dimension(&mut self, dimension: Dimension) -> Result {
+        self.world.require_raspberry_jam().await?;
+        self.world
+            .send_command(raspberry_jam::PlayerSetDimension { dimension })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+
+    #[tokio::test]
+    async fn client_player_inventory() {
+        let connection = MockConnection::new(ServerKind::McpiAddons).with_response("35|14|64");
+        let mut player = World::new(connection).me();
+        assert_eq!(
+            player.held_item().await.unwrap(),
+            ItemStack {
+                id: 35,
+                auxiliary: 14,
+                count: 64
+            }
+        );
+        player.give(1, 0, 32).await.unwrap();
+        assert_eq!(
+            player.into_inner().connection().await.sent,
+            [
+                "custom.inventory.getSlot()\n",
+                "custom.inventory.give(1|0|32)\n"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn client_player_health_and_gamemode() {
+        let connection = MockConnection::new(ServerKind::McpiAddons)
+            .with_response("20")
+            .with_response("1");
+        let mut player = World::new(connection).me();
+        assert_eq!(player.health().await.unwrap(), 20);
+        player.set_health(10).await.unwrap();
+        assert_eq!(player.gamemode().await.unwrap(), Gamemode::Creative);
+        assert_eq!(
+            player.into_inner().connection().await.sent,
+            [
+                "custom.player.getHealth()\n",
+                "custom.player.setHealth(10)\n",
+                "custom.player.getGamemode()\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn entity_age_and_sheep_color() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        let mut sheep = EntityId(5).into_entity(world.clone());
+        sheep.set_age(Age::Baby).await.unwrap();
+        sheep.set_age(Age::Ticks(-100)).await.unwrap();
+        sheep.set_sheep_color(SheepColor::RED).await.unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.entity.setAge(5,-24000)\n",
+                "custom.entity.setAge(5,-100)\n",
+                "custom.entity.setSheepColor(5,14)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn player_name_and_uuid() {
+        let connection = MockConnection::new(ServerKind::RaspberryJam)
+            .with_response("alice,069a79f4-44e9-4726-a5be-fca90e38aaf5")
+            .with_response("alice,069a79f4-44e9-4726-a5be-fca90e38aaf5");
+        let player = EntityId(1).into_player(World::new(connection));
+        assert_eq!(player.name().await.unwrap(), "alice");
+        assert_eq!(
+            player.uuid().await.unwrap(),
+            "069a79f4-44e9-4726-a5be-fca90e38aaf5"
+        );
+        assert_eq!(
+            player.into_inner().connection().await.sent,
+            ["entity.getNameAndUUID(1)\n", "entity.getNameAndUUID(1)\n"]
+        );
+    }
+
+    #[tokio::test]
+    async fn set_dimension() {
+        let world = World::new(MockConnection::new(ServerKind::RaspberryJam));
+        world.me().set_dimension(Dimension::Nether).await.unwrap();
+        EntityId(3)
+            .into_player(world.clone())
+            .set_dimension(Dimension::End)
+            .await
+            .unwrap();
+        EntityId(4)
+            .into_entity(world.clone())
+            .set_dimension(Dimension::Overworld)
+            .await
+            .unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "player.setDimension(-1)\n",
+                "entity.setDimension(3,1)\n",
+                "entity.setDimension(4,0)\n",
+            ]
+        );
+
+        let mut player = World::new(MockConnection::new(ServerKind::Vanilla)).me();
+        assert!(matches!(
+            player.set_dimension(Dimension::Nether).await,
+            Err(WorldError::Unsupported {
+                server_kind: ServerKind::Vanilla
+            })
+        ));
+    }
+
+    #[test]
+    fn gamemode_from_id() {
+        assert_eq!(Gamemode::from(0), Gamemode::Survival);
+        assert_eq!(Gamemode::from(7), Gamemode::Unknown(7));
+        assert_eq!(i32::from(Gamemode::Creative), 1);
+        assert_eq!(Gamemode::from(Gamemode::Creative.id()), Gamemode::Creative);
+    }
+
+    #[test]
+    fn movement_tracker_first_sample_is_stationary() {
+        let mut tracker = MovementTracker::new();
+        let sample = tracker
+            .update(Point3::new(1.5, 2.0, 3.5), Instant::now())
+            .unwrap();
+        assert_eq!(sample.tile, Point3::new(1, 2, 3));
+        assert_eq!(sample.velocity, Vector3::zeros());
+        assert!(!sample.on_tile_change);
+    }
+
+    #[test]
+    fn movement_tracker_computes_velocity() {
+        let mut tracker = MovementTracker::new();
+        let start = Instant::now();
+        tracker.update(Point3::new(0.5, 0.0, 0.5), start);
+        let sample = tracker
+            .update(
+                Point3::new(1.5, 0.0, 0.5),
+                start + Duration::from_millis(500),
+            )
+            .unwrap();
+        assert_eq!(sample.velocity, Vector3::new(2.0, 0.0, 0.0));
+        assert!(sample.on_tile_change);
+    }
+
+    #[test]
+    fn movement_tracker_filters_jitter() {
+        let mut tracker = MovementTracker::with_threshold(0.5);
+        let start = Instant::now();
+        tracker.update(Point3::new(0.1, 0.0, 0.5), start);
+        let sample = tracker.update(
+            Point3::new(0.2, 0.0, 0.5),
+            start + Duration::from_millis(100),
+        );
+        assert_eq!(sample, None);
+
+        // Small movements accumulate until they pass the threshold.
+        let sample = tracker
+            .update(
+                Point3::new(0.6, 0.0, 0.5),
+                start + Duration::from_millis(250),
+            )
+            .unwrap();
+        assert_eq!(sample.velocity, Vector3::new(2.0, 0.0, 0.0));
+        assert!(!sample.on_tile_change);
+    }
+}
@@ -0,0 +1,296 @@
+//! Scripted keyboard input for the host player, using the MCPI Addons API
+//! extension.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use crate::connection::commands::mcpi_addons::{CustomKeyPress, CustomKeyRelease};
+use crate::connection::{MCPIExtrasKey, Protocol};
+use crate::util::spawn_cleanup;
+use crate::{Result, World};
+
+/// How long a key is held down for when it is tapped (one game tick).
+pub const TAP_DURATION: Duration = Duration::from_millis(50);
+
+type Key = MCPIExtrasKey<'static>;
+
+/// A single action in an [`InputSequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputStep {
+    /// Presses a key until it is released by a later step.
+    Press(Key),
+    /// Releases a key.
+    Release(Key),
+    /// Presses a key and releases it after the given duration.
+    Hold(Key, Duration),
+    /// Presses and quickly releases a key.
+    Tap(Key),
+    /// Waits for the given duration.
+    Wait(Duration),
+}
+
+/// A series of inputs that can be run by an [`InputController`].
+///
+/// # Example
+///
+/// ```
+/// # use mcpi::input::InputSequence;
+/// # use std::time::Duration;
+/// // Walk forward for 2 seconds, then jump.
+/// let sequence = InputSequence::new()
+///     .walk_forward(Duration::from_secs(2))
+///     .jump();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct InputSequence {
+    steps: Vec<InputStep>,
+}
+
+impl InputSequence {
+    /// Creates an empty sequence.
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Adds a step to the end of the sequence.
+    pub fn then(mut self, step: InputStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Returns the steps in the sequence.
+    pub fn steps(&self) -> &[InputStep] {
+        &self.steps
+    }
+
+    /// Presses a key until it is released by a later step.
+    pub fn press(self, key: Key) -> Self {
+        self.then(InputStep::Press(key))
+    }
+
+    /// Releases a key.
+    pub fn release(self, key: Key) -> Self {
+        self.then(InputStep::Release(key))
+    }
+
+    /// Presses a key and releases it after the given duration.
+    pub fn hold(self, key: Key, duration: Duration) -> Self {
+        self.then(InputStep::Hold(key, duration))
+    }
+
+    /// Presses and quickly releases a key.
+    pub fn tap(self, key: Key) -> Self {
+        self.then(InputStep::Tap(key))
+    }
+
+    /// Waits for the given duration.
+    pub fn wait(self, duration: Duration) -> Self {
+        self.then(InputStep::Wait(duration))
+    }
+
+    /// Walks forward for the given duration.
+    pub fn walk_forward(self, duration: Duration) -> Self {
+        self.hold(MCPIExtrasKey::W, duration)
+    }
+
+    /// Walks backward for the given duration.
+    pub fn walk_backward(self, duration: Duration) -> Self {
+        self.hold(MCPIExtrasKey::S, duration)
+    }
+
+    /// Walks to the left for the given duration.
+    pub fn strafe_left(self, duration: Duration) -> Self {
+        self.hold(MCPIExtrasKey::A, duration)
+    }
+
+    /// Walks to the right for the given duration.
+    pub fn strafe_right(self, duration: Duration) -> Self {
+        self.hold(MCPIExtrasKey::D, duration)
+    }
+
+    /// Sneaks for the given duration.
+    pub fn sneak(self, duration: Duration) -> Self {
+        self.hold(MCPIExtrasKey::LSHIFT, duration)
+    }
+
+    /// Jumps once.
+    pub fn jump(self) -> Self {
+        self.tap(MCPIExtrasKey::SPACE)
+    }
+}
+
+/// Simulates keyboard input for the host player.
+///
+/// The controller keeps track of every key it has pressed. If a sequence is
+/// cancelled (by dropping the future returned by [`Self::run`]) or the
+/// controller is dropped, all keys that are still pressed are released in a
+/// background task.
+///
+/// MCPI Addons server only!
+#[derive(Debug)]
+pub struct InputController<T: Protocol + Send + 'static> {
+    world: World<T>,
+    pressed: Arc<Mutex<HashSet<Key>>>,
+}
+
+impl<T: Protocol + Send + 'static> InputController<T> {
+    pub fn new(world: World<T>) -> Self {
+        Self {
+            world,
+            pressed: Arc::default(),
+        }
+    }
+
+    fn pressed(&self) -> MutexGuard<'_, HashSet<Key>> {
+        self.pressed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns whether the controller is currently holding down the given key.
+    pub fn is_pressed(&self, key: Key) -> bool {
+        self.pressed().contains(&key)
+    }
+
+    /// Presses a key until it is released.
+    pub async fn press(&mut self, key: Key) -> Result {
+        self.world.require_mcpi_addons().await?;
+        // Mark the key as pressed first so that it is released even if this
+        // future is cancelled while the command is being sent.
+        self.pressed().insert(key);
+        self.world.send_command(CustomKeyPress { key }).await?;
+        Ok(())
+    }
+
+    /// Releases a key.
+    pub async fn release(&mut self, key: Key) -> Result {
+        self.world.require_mcpi_addons().await?;
+        self.world.send_command(CustomKeyRelease { key }).await?;
+        self.pressed().remove(&key);
+        Ok(())
+    }
+
+    /// Releases every key that is currently pressed.
+    pub async fn release_all(&mut self) -> Result {
+        let keys = self.pressed().iter().copied().collect::<Vec<_>>();
+        for key in keys {
+            self.release(key).await?;
+        }
+        Ok(())
+    }
+
+    /// Presses a key and releases it after the given duration.
+    pub async fn hold(&mut self, key: Key, duration: Duration) -> Result {
+        self.press(key).await?;
+        sleep(duration).await;
+        self.release(key).await
+    }
+
+    /// Presses and quickly releases a key.
+    pub async fn tap(&mut self, key: Key) -> Result {
+        self.hold(key, TAP_DURATION).await
+    }
+
+    /// Runs each step of the given sequence in order.
+    ///
+    /// Once the sequence ends, fails, or is cancelled, every key that is
+    /// still pressed is released.
+    pub async fn run(&mut self, sequence: &InputSequence) -> Result {
+        let guard = ReleaseGuard {
+            world: self.world.clone(),
+            pressed: self.pressed.clone(),
+        };
+        for step in sequence.steps() {
+            match *step {
+                InputStep::Press(key) => self.press(key).await?,
+                InputStep::Release(key) => self.release(key).await?,
+                InputStep::Hold(key, duration) => self.hold(key, duration).await?,
+                InputStep::Tap(key) => self.tap(key).await?,
+                InputStep::Wait(duration) => sleep(duration).await,
+            }
+        }
+        self.release_all().await?;
+        drop(guard);
+        Ok(())
+    }
+}
+
+impl<T: Protocol + Send + 'static> Drop for InputController<T> {
+    fn drop(&mut self) {
+        release_in_background(&self.world, &self.pressed);
+    }
+}
+
+/// Releases pressed keys if a sequence does not finish.
+struct ReleaseGuard<T: Protocol + Send + 'static> {
+    world: World<T>,
+    pressed: Arc<Mutex<HashSet<Key>>>,
+}
+
+impl<T: Protocol + Send + 'static> Drop for ReleaseGuard<T> {
+    fn drop(&mut self) {
+        release_in_background(&self.world, &self.pressed);
+    }
+}
+
+/// Spawns a task that releases all of the given pressed keys.
+fn release_in_background<T: Protocol + Send + 'static>(
+    world: &World<T>,
+    pressed: &Mutex<HashSet<Key>>,
+) {
+    let keys = std::mem::take(&mut *pressed.lock().unwrap_or_else(|e| e.into_inner()));
+    if keys.is_empty() {
+        return;
+    }
+    spawn_cleanup(world, |world| async move {
+        for key in keys {
+            world.send_command(CustomKeyRelease { key }).await?;
+        }
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+
+    #[tokio::test]
+    async fn input_sequence_releases_keys() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        let mut input = world.me().input();
+        let sequence = InputSequence::new()
+            .press(MCPIExtrasKey::LSHIFT)
+            .tap(MCPIExtrasKey::W);
+        input.run(&sequence).await.unwrap();
+        assert!(!input.is_pressed(MCPIExtrasKey::LSHIFT));
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.key.press(LSHIFT)\n",
+                "custom.key.press(W)\n",
+                "custom.key.release(W)\n",
+                "custom.key.release(LSHIFT)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_input_sequence_releases_keys() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        let mut input = world.me().input();
+        let sequence = InputSequence::new().walk_forward(Duration::from_secs(60));
+        let cancelled = tokio::time::timeout(Duration::from_millis(10), input.run(&sequence)).await;
+        assert!(cancelled.is_err());
+
+        // Let the background task run.
+        tokio::task::yield_now().await;
+        assert!(!input.is_pressed(MCPIExtrasKey::W));
+        assert_eq!(
+            world.connection().await.sent,
+            ["custom.key.press(W)\n", "custom.key.release(W)\n"]
+        );
+    }
+}
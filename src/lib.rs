@@ -1,28 +1,47 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 #![warn(rust_2018_idioms, /* missing_docs, */ clippy::missing_const_for_fn, rust_2024_compatibility)]
 
+use std::collections::HashMap;
 use std::num::{ParseFloatError, ParseIntError};
 use std::str::FromStr;
+use std::str::ParseBoolError;
 use std::sync::Arc;
 use std::time::Duration;
 
+use block::nbt::{InvalidNbtError, SignNbt};
+use block::sign::{validate_sign_line, MAX_SIGN_LINES};
+use block::state::{BlockState, Facing};
 use block::{BlockFace, InvalidBlockFaceError, ParseBlockError};
+use camera::{Camera, CameraMode};
 use connection::commands::*;
 use connection::{
     ApiStr, ChatString, ConnectOptions, ConnectionError, EntityId, NewlineStrError, Protocol,
-    ServerConnection, Tile, TileData, WorldSettingKey,
+    ServerConnection, ServerKind, Tile, TileData, WorldSettingKey,
 };
-use derive_more::derive::From;
 use entity::{ClientPlayer, Player};
 use futures_core::Stream;
 use itertools::Itertools;
-use nalgebra::{Point2, Point3};
-use snafu::{OptionExt, Snafu};
+use lighting::LightLevels;
+use nalgebra::{distance, Point2, Point3};
+use overrides::Overrides;
+use particle::ParticleSpec;
+use reborn::{ParseRebornVersionError, RebornVersion};
+use region::Region;
+use snafu::{ensure, OptionExt, Snafu};
 
 pub mod block;
 pub mod camera;
 pub mod connection;
 pub mod entity;
+pub mod input;
+pub mod lighting;
+#[cfg(feature = "log")]
+pub mod logger;
+pub mod overrides;
+pub mod particle;
+pub mod reborn;
+pub mod region;
+pub mod trigger;
 pub mod util;
 
 pub use block::Block;
@@ -49,24 +68,85 @@ pub enum WorldError {
     /// An error caused by failing to parse a block returned by the server.
     #[snafu(display("{source}"), context(false))]
     ParseBlock { source: ParseBlockError },
+    /// An error caused by failing to parse a boolean from a string.
+    #[snafu(display("{source}"), context(false))]
+    ParseBool { source: ParseBoolError },
+    /// An error caused by failing to parse a Reborn version returned by the
+    /// server.
+    #[snafu(display("{source}"), context(false))]
+    ParseRebornVersion { source: ParseRebornVersionError },
+    /// NBT data could not be sent to the server.
+    #[snafu(display("{source}"), context(false))]
+    InvalidNbt { source: InvalidNbtError },
     /// There was not enough data in the server's response.
     NotEnoughParts,
     /// A block face returned by the server was invalid.
     #[snafu(display("{source}"), context(false))]
     InvalidBlockFace { source: InvalidBlockFaceError },
+    /// The operation requires an API extension that the server does not
+    /// support.
+    #[snafu(display("This operation is not supported by {server_kind:?} servers."))]
+    Unsupported { server_kind: ServerKind },
+    /// A frame rate was not a positive, finite number, or was too high to
+    /// wait between frames.
+    #[snafu(display("Frame rate {frame_rate} is not a usable number of frames per second."))]
+    InvalidFrameRate { frame_rate: f64 },
+    /// The spacing between particles was not a positive number, or was so
+    /// small that the trail would need too many particles.
+    #[snafu(display("Particle spacing {spacing} is not positive or is too small for the trail."))]
+    InvalidParticleSpacing { spacing: f64 },
+    /// More lines of text were given than fit on a sign.
+    #[snafu(display("Signs can have at most {MAX_SIGN_LINES} lines, but {count} were given."))]
+    TooManySignLines { count: usize },
+    /// A line of sign text cannot be written to a sign.
+    #[snafu(display("Invalid sign line {line:?}: {reason}."))]
+    InvalidSignLine { line: String, reason: String },
+    /// A light level was outside of the range from 0.0 to 1.0.
+    #[snafu(display("Light level {level} is not between 0.0 and 1.0."))]
+    InvalidLightLevel { level: f32 },
+}
+
+/// Serializes a block's NBT data, checking that it can be sent to the server.
+fn checked_json_nbt(block: &Block) -> Result<Option<String>> {
+    if let Some(nbt) = &block.nbt {
+        block::nbt::validate(nbt)?;
+    }
+    Ok(block.json_nbt())
+}
+
+/// Checks that a light level is between 0.0 and 1.0.
+fn validate_light_level(level: f32) -> Result {
+    ensure!(
+        (0.0..=1.0).contains(&level),
+        InvalidLightLevelSnafu { level }
+    );
+    Ok(())
 }
 
 pub type Result<T = (), E = WorldError> = std::result::Result<T, E>;
 
-#[derive(Debug, From)]
+#[derive(Debug)]
 pub struct World<T: Protocol = ServerConnection> {
     connection: Arc<Mutex<T>>,
+    state: Arc<std::sync::Mutex<WorldState>>,
+}
+
+/// Client-side state that is shared between all clones of a [`World`].
+#[derive(Debug, Default)]
+struct WorldState {
+    /// The last mode set using a [`Camera`].
+    camera_mode: Option<CameraMode>,
+    /// The server's Reborn version, once it has been queried.
+    reborn_version: Option<RebornVersion>,
+    /// Reborn features that have been queried, by name.
+    reborn_features: HashMap<String, bool>,
 }
 
 impl<T: Protocol> Clone for World<T> {
     fn clone(&self) -> Self {
         Self {
             connection: self.connection.clone(),
+            state: self.state.clone(),
         }
     }
 }
@@ -77,25 +157,67 @@ impl<T: Protocol> From<T> for World<T> {
     }
 }
 
+impl<T: Protocol> From<Arc<Mutex<T>>> for World<T> {
+    fn from(connection: Arc<Mutex<T>>) -> Self {
+        Self {
+            connection,
+            state: Arc::default(),
+        }
+    }
+}
+
 impl World<ServerConnection> {
     pub async fn connect(addr: impl ToSocketAddrs) -> std::io::Result<Self> {
-        Ok(Self::new(
-            ServerConnection::new(addr, ConnectOptions::default()).await?,
-        ))
+        Self::connect_with(addr, ConnectOptions::default()).await
+    }
+
+    /// Connects to the server at the given address, changing the behavior of
+    /// the connection with the given options.
+    pub async fn connect_with(
+        addr: impl ToSocketAddrs,
+        options: ConnectOptions,
+    ) -> std::io::Result<Self> {
+        Ok(Self::new(ServerConnection::new(addr, options).await?))
     }
 }
 
 impl<T: Protocol> World<T> {
     pub fn new(connection: T) -> Self {
-        Self {
-            connection: Arc::new(Mutex::new(connection)),
-        }
+        Self::from(Arc::new(Mutex::new(connection)))
     }
 
     pub async fn connection(&self) -> MutexGuard<'_, T> {
         self.connection.lock().await
     }
 
+    /// Returns the kind of server the world is connected to.
+    pub async fn server_kind(&self) -> ServerKind {
+        self.connection().await.server_kind()
+    }
+
+    /// Fails with [`WorldError::Unsupported`] unless the server's kind passes
+    /// the given check.
+    async fn require(&self, check: fn(ServerKind) -> bool) -> Result {
+        let server_kind = self.server_kind().await;
+        if check(server_kind) {
+            Ok(())
+        } else {
+            UnsupportedSnafu { server_kind }.fail()
+        }
+    }
+
+    pub(crate) async fn require_raspberry_jam(&self) -> Result {
+        self.require(ServerKind::supports_raspberry_jam).await
+    }
+
+    pub(crate) async fn require_mcpi_addons(&self) -> Result {
+        self.require(ServerKind::supports_mcpi_addons).await
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, WorldState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     pub async fn send_command(
         &self,
         command: impl SerializableCommand,
@@ -113,13 +235,36 @@ impl<T: Protocol> World<T> {
     /// substituted for the `?` symbol.
     #[doc(alias("chat", "say", "send"))]
     pub async fn post(&mut self, text: &str) -> Result<(), WorldError> {
+        self.post_to(text, MessageTarget::Everyone).await
+    }
+
+    /// Post one or more messages to the in-game chat, choosing who sees them
+    /// and how they are shown.
+    ///
+    /// Messages are split and re-encoded in the same way as [`Self::post`].
+    ///
+    /// [`MessageTarget::Client`] and [`MessageTarget::NoPrefix`] are MCPI
+    /// Addons server only!
+    pub async fn post_to(&mut self, text: &str, target: MessageTarget) -> Result<(), WorldError> {
+        if target != MessageTarget::Everyone {
+            self.require_mcpi_addons().await?;
+        }
         let messages = text
             .split('\n')
             .map(ChatString::from_str_lossy)
             .collect::<Vec<_>>();
         let mut conn = self.connection().await;
         for message in messages {
-            conn.send(ChatPost { message }).await?;
+            match target {
+                MessageTarget::Everyone => conn.send(ChatPost { message }).await?,
+                MessageTarget::Client => {
+                    conn.send(mcpi_addons::CustomPostClient { message }).await?
+                }
+                MessageTarget::NoPrefix => {
+                    conn.send(mcpi_addons::CustomPostNoPrefix { message })
+                        .await?
+                }
+            };
         }
         Ok(())
     }
@@ -172,6 +317,9 @@ impl<T: Protocol> World<T> {
     }
 
     /// Gets the type and metadata of the block at the given coordinates.
+    ///
+    /// On Raspberry Jam servers, the block's NBT data is also included while
+    /// the [`WorldSettingKey::INCLUDE_NBT_WITH_DATA`] setting is enabled.
     pub async fn get_block(&self, coords: Point3<i16>) -> Result<Block> {
         Ok(self
             .send_command(WorldGetBlockWithData { coords })
@@ -179,6 +327,23 @@ impl<T: Protocol> World<T> {
             .parse()?)
     }
 
+    /// Gets the blocks at several coordinates.
+    ///
+    /// The API has no bulk query, so this sends one command per block, one
+    /// after the other. The connection stays locked until every block has
+    /// been received, so other commands cannot be interleaved.
+    pub async fn get_blocks(
+        &self,
+        coords: impl IntoIterator<Item = Point3<i16>>,
+    ) -> Result<Vec<Block>> {
+        let mut conn = self.connection().await;
+        let mut blocks = Vec::new();
+        for coords in coords {
+            blocks.push(conn.send(WorldGetBlockWithData { coords }).await?.parse()?);
+        }
+        Ok(blocks)
+    }
+
     /// Sets the block at the given coordinates to the specified type.
     ///
     /// This method is shorthand for [`Self::set_block`] with `Block::new(tile,
@@ -224,7 +389,7 @@ impl<T: Protocol> World<T> {
         coords_2: Point3<i16>,
         block: &Block,
     ) -> Result<()> {
-        let nbt = block.json_nbt();
+        let nbt = checked_json_nbt(block)?;
         self.send_command(WorldSetBlocks {
             coords_1,
             coords_2,
@@ -239,7 +404,7 @@ impl<T: Protocol> World<T> {
     /// Updates the block at the given coordinates to have the specified type
     /// and metadata.
     pub async fn set_block(&mut self, coords: Point3<i16>, block: &Block) -> Result<()> {
-        let nbt = block.json_nbt();
+        let nbt = checked_json_nbt(block)?;
         self.send_command(WorldSetBlock {
             coords,
             tile: block.tile,
@@ -250,6 +415,68 @@ impl<T: Protocol> World<T> {
         Ok(())
     }
 
+    /// Places every block of a region, with the region's minimum corner at
+    /// `origin`.
+    pub async fn set_region(&mut self, origin: Point3<i16>, region: &Region) -> Result<()> {
+        let mut conn = self.connection().await;
+        for (position, block) in region.iter() {
+            let nbt = checked_json_nbt(block)?;
+            conn.send(WorldSetBlock {
+                coords: origin + position.coords.cast(),
+                tile: block.tile,
+                data: block.data,
+                json_nbt: nbt.as_deref().map(ApiStr::new).transpose()?,
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Places a wall sign facing the given direction, with up to four lines of
+    /// text that are each at most 15 characters long.
+    ///
+    /// Raspberry Juice and Raspberry Jam servers only! Raspberry Jam servers
+    /// receive the text as NBT data, while Raspberry Juice servers cannot write
+    /// lines that contain commas.
+    pub async fn set_sign(
+        &mut self,
+        coords: Point3<i16>,
+        facing: Facing,
+        lines: &[&str],
+    ) -> Result {
+        ensure!(
+            lines.len() <= MAX_SIGN_LINES,
+            TooManySignLinesSnafu { count: lines.len() }
+        );
+        let block = Block::from(BlockState::WallSign { facing });
+
+        let server_kind = self.server_kind().await;
+        if server_kind.supports_raspberry_jam() {
+            for line in lines {
+                validate_sign_line(line, true)?;
+            }
+            let nbt = SignNbt::new(lines.iter().copied());
+            self.set_block(coords, &block.with_nbt(nbt.into())).await
+        } else if server_kind.supports_raspberry_juice() {
+            for line in lines {
+                validate_sign_line(line, false)?;
+            }
+            self.send_command(raspberry_juice::WorldSetSign {
+                coords,
+                tile: block.tile,
+                data: block.data,
+                lines: lines
+                    .iter()
+                    .map(|line| ApiStr::new(line))
+                    .collect::<Result<_, _>>()?,
+            })
+            .await?;
+            Ok(())
+        } else {
+            UnsupportedSnafu { server_kind }.fail()
+        }
+    }
+
     /// Finds the Y-coordinate of the highest non-air block at the given X and Z
     /// coordinates.
     pub async fn get_height_at(&self, coords: Point2<i16>) -> Result<i16> {
@@ -257,12 +484,63 @@ impl<T: Protocol> World<T> {
         Ok(y.parse()?)
     }
 
+    /// Checks whether a player could safely stand with their feet at the given
+    /// coordinates. See [`block::properties::can_stand_on`].
+    ///
+    /// Coordinates with no tile above or below them, at the bounds of `i16`,
+    /// are never safe.
+    pub async fn can_stand_at(&self, coords: Point3<i16>) -> Result<bool> {
+        let (Some(below), Some(above)) = (coords.y.checked_sub(1), coords.y.checked_add(1)) else {
+            return Ok(false);
+        };
+        let ground = self
+            .get_tile(Point3::new(coords.x, below, coords.z))
+            .await?;
+        let feet = self.get_tile(coords).await?;
+        let head = self
+            .get_tile(Point3::new(coords.x, above, coords.z))
+            .await?;
+        Ok(block::properties::can_stand_on(ground, feet, head))
+    }
+
+    /// Gets the level of light emitted by a tile, from 0.0 (none) to 1.0
+    /// (as bright as glowstone).
+    ///
+    /// Raspberry Jam server only!
+    pub async fn tile_light_level(&self, tile: Tile) -> Result<f32> {
+        self.require_raspberry_jam().await?;
+        let level = self
+            .send_command(raspberry_jam::BlockGetLightLevel { tile })
+            .await?;
+        Ok(level.parse()?)
+    }
+
+    /// Changes the level of light emitted by every block of a tile, from 0.0
+    /// (none) to 1.0 (as bright as glowstone).
+    ///
+    /// The change lasts until the game is restarted. Use [`Self::light_levels`]
+    /// to restore the original levels automatically.
+    ///
+    /// Raspberry Jam server only!
+    pub async fn set_tile_light_level(&mut self, tile: Tile, level: f32) -> Result {
+        validate_light_level(level)?;
+        self.require_raspberry_jam().await?;
+        self.send_command(raspberry_jam::BlockSetLightLevel { tile, level })
+            .await?;
+        Ok(())
+    }
+
     /// Returns the player entity controlled by the connected game instance
     /// (i.e. the host player).
     pub fn me(&self) -> ClientPlayer<T> {
         ClientPlayer::new(self.clone())
     }
 
+    /// Returns the camera of the connected game instance.
+    pub fn camera(&self) -> Camera<T> {
+        Camera::new(self.clone())
+    }
+
     pub async fn save_checkpoint(&mut self) -> Result<()> {
         self.send_command(WorldCheckpointSave {}).await?;
         Ok(())
@@ -273,6 +551,160 @@ impl<T: Protocol> World<T> {
         Ok(())
     }
 
+    /// Gets the name of the world.
+    ///
+    /// MCPI Addons server only!
+    pub async fn name(&self) -> Result<String> {
+        self.require_mcpi_addons().await?;
+        Ok(self.send_command(mcpi_addons::CustomWorldName {}).await?)
+    }
+
+    /// Gets the name of the directory the world is saved in.
+    ///
+    /// MCPI Addons server only!
+    pub async fn directory(&self) -> Result<String> {
+        self.require_mcpi_addons().await?;
+        Ok(self.send_command(mcpi_addons::CustomWorldDir {}).await?)
+    }
+
+    /// Gets the name of the server the game is connected to, if it is playing
+    /// on a multiplayer server.
+    ///
+    /// MCPI Addons server only!
+    pub async fn server_name(&self) -> Result<String> {
+        self.require_mcpi_addons().await?;
+        Ok(self
+            .send_command(mcpi_addons::CustomWorldServername {})
+            .await?)
+    }
+
+    /// Gets the version of Minecraft: Pi Edition: Reborn the game is running.
+    ///
+    /// The version is cached after it is first queried.
+    ///
+    /// MCPI Addons server only!
+    pub async fn reborn_version(&self) -> Result<RebornVersion> {
+        if let Some(version) = self.state().reborn_version {
+            return Ok(version);
+        }
+        self.require_mcpi_addons().await?;
+        let version: RebornVersion = self
+            .send_command(mcpi_addons::CustomRebornVersion {})
+            .await?
+            .parse()?;
+        self.state().reborn_version = Some(version);
+        Ok(version)
+    }
+
+    /// Checks whether the game has the Minecraft: Pi Edition: Reborn feature
+    /// with the given name enabled.
+    ///
+    /// Results are cached after each feature is first queried.
+    ///
+    /// MCPI Addons server only!
+    pub async fn reborn_has_feature(&self, name: &str) -> Result<bool> {
+        if let Some(&enabled) = self.state().reborn_features.get(name) {
+            return Ok(enabled);
+        }
+        self.require_mcpi_addons().await?;
+        let response = self
+            .send_command(mcpi_addons::CustomRebornFeature {
+                feature_name: ApiStr::new(name)?,
+            })
+            .await?;
+        let enabled = match response.as_str() {
+            "1" => true,
+            "0" => false,
+            response => response.parse()?,
+        };
+        self.state()
+            .reborn_features
+            .insert(name.to_owned(), enabled);
+        Ok(enabled)
+    }
+
+    /// Spawns particles as described by `spec`.
+    ///
+    /// MCPI Addons ignores the spec's direction and speed, and spawns each
+    /// particle individually at the same position. This sends one command per
+    /// particle while holding the connection, so other commands wait until
+    /// all of them have been sent; keep the count small.
+    ///
+    /// MCPI Addons or Raspberry Jam server only! Fails with
+    /// [`WorldError::Unsupported`] if the server cannot show the particle.
+    pub async fn spawn_particles(&mut self, spec: &ParticleSpec) -> Result {
+        let server_kind = self.server_kind().await;
+        if server_kind.supports_raspberry_jam() {
+            let particle = spec
+                .particle
+                .raspberry_jam()
+                .context(UnsupportedSnafu { server_kind })?;
+            self.send_command(raspberry_jam::WorldSpawnParticle {
+                particle,
+                coords: spec.position,
+                direction: Point3::from(spec.direction),
+                speed: spec.speed,
+                count: i32::try_from(spec.count).unwrap_or(i32::MAX),
+            })
+            .await?;
+        } else if server_kind.supports_mcpi_addons() {
+            let particle = spec
+                .particle
+                .mcpi_addons()
+                .context(UnsupportedSnafu { server_kind })?;
+            let coords = spec.position.cast();
+            let mut conn = self.connection().await;
+            for _ in 0..spec.count {
+                conn.send(mcpi_addons::CustomWorldParticle { particle, coords })
+                    .await?;
+            }
+        } else {
+            return UnsupportedSnafu { server_kind }.fail();
+        }
+        Ok(())
+    }
+
+    /// Spawns particles at evenly spaced points along the line from `start` to
+    /// `end`, ignoring the spec's position.
+    ///
+    /// See [`Self::spawn_particles`] and [`particle::line`]. Fails with
+    /// [`WorldError::InvalidParticleSpacing`] if `spacing` is not positive, or
+    /// if the trail would need more than [`particle::MAX_LINE_SEGMENTS`]
+    /// segments.
+    pub async fn spawn_particle_trail(
+        &mut self,
+        spec: &ParticleSpec,
+        start: Point3<f64>,
+        end: Point3<f64>,
+        spacing: f64,
+    ) -> Result {
+        let segments = distance(&start, &end) / spacing;
+        ensure!(
+            spacing > 0.0 && segments <= particle::MAX_LINE_SEGMENTS as f64,
+            InvalidParticleSpacingSnafu { spacing }
+        );
+        for position in particle::line(start, end, spacing) {
+            self.spawn_particles(&spec.at(position)).await?;
+        }
+        Ok(())
+    }
+
+    /// Spawns particles at `points` evenly spaced points on a horizontal circle
+    /// around the spec's position.
+    ///
+    /// See [`Self::spawn_particles`] and [`particle::ring`].
+    pub async fn spawn_particle_ring(
+        &mut self,
+        spec: &ParticleSpec,
+        radius: f64,
+        points: usize,
+    ) -> Result {
+        for position in particle::ring(spec.position, radius, points) {
+            self.spawn_particles(&spec.at(position)).await?;
+        }
+        Ok(())
+    }
+
     /// Returns all players currently in the world.
     pub async fn all_players(&self) -> Result<Vec<Player<T>>> {
         let ids = self.send_command(WorldGetPlayerIds {}).await?;
@@ -286,6 +718,45 @@ impl<T: Protocol> World<T> {
         Ok(players)
     }
 
+    /// Finds the player with the given name, returning [`None`] if they are
+    /// not in the world.
+    ///
+    /// Raspberry Juice or Raspberry Jam server only!
+    pub async fn player_by_name(&self, name: &str) -> Result<Option<Player<T>>> {
+        self.require(ServerKind::supports_raspberry_juice).await?;
+        let id = self
+            .send_command(raspberry_juice::WorldGetPlayerId {
+                name: Some(ApiStr::new(name)?),
+            })
+            .await?;
+        if id == "Fail" {
+            return Ok(None);
+        }
+        Ok(Some(Player::new(self.clone(), EntityId(id.parse()?))))
+    }
+
+    /// Returns the names of all players currently in the world.
+    ///
+    /// MCPI Addons, Raspberry Juice or Raspberry Jam server only!
+    pub async fn player_names(&self) -> Result<Vec<String>> {
+        let server_kind = self.server_kind().await;
+        if server_kind.supports_mcpi_addons() {
+            let names = self.send_command(mcpi_addons::CustomUsernameAll {}).await?;
+            if names.is_empty() {
+                return Ok(Vec::new());
+            }
+            return Ok(names.split('|').map(str::to_owned).collect());
+        }
+        if !server_kind.supports_raspberry_juice() {
+            return UnsupportedSnafu { server_kind }.fail();
+        }
+        let mut names = Vec::new();
+        for player in self.all_players().await? {
+            names.push(player.name().await?);
+        }
+        Ok(names)
+    }
+
     /// Enables or disables a setting that controls the behavior or the game
     /// world.
     pub async fn set(&mut self, setting: WorldSettingKey<'_>, enabled: bool) -> Result<()> {
@@ -323,6 +794,7 @@ impl<T: Protocol> World<T> {
     pub async fn poll_block_hits(&self) -> Result<Vec<BlockHit>> {
         let hits = self.send_command(EventsBlockHits {}).await?;
         hits.split('|')
+            .filter(|hit| !hit.is_empty())
             .map(|hit| {
                 let [x, y, z, face, player_id] = hit
                     .split(',')
@@ -343,26 +815,60 @@ impl<T: Protocol> World<T> {
     /// # Arguments
     ///
     /// * `interval` - The interval at which to poll for block hits.
-    pub fn block_hits(&self, interval: Duration) -> impl Stream<Item = Result<BlockHit>> {
+    pub fn block_hits(&self, interval: Duration) -> impl Stream<Item = Result<BlockHit>> + use<T> {
+        let batches = self.block_hit_batches(interval);
+        async_stream::try_stream! {
+            for await hits in batches {
+                for hit in hits? {
+                    yield hit;
+                }
+            }
+        }
+    }
+
+    /// Creates a stream of block hit events along with the block that was hit
+    /// and the player that hit it.
+    ///
+    /// The blocks of all hits that arrive in the same poll are looked up
+    /// together with [`Self::get_blocks`]. See [`Self::block_hits`].
+    pub fn block_hits_with_blocks(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<ResolvedBlockHit<T>>> + use<T> {
+        let world = self.clone();
+        let batches = self.block_hit_batches(interval);
+        async_stream::try_stream! {
+            for await hits in batches {
+                let hits = hits?;
+                let blocks = world.get_blocks(hits.iter().map(|hit| hit.location)).await?;
+                for (hit, block) in hits.into_iter().zip(blocks) {
+                    let player = hit.player(&world);
+                    yield ResolvedBlockHit { hit, block, player };
+                }
+            }
+        }
+    }
+
+    /// Polls for block hits at the given interval, yielding the hits of each
+    /// poll that had any. Polls are skipped while the event queue is full.
+    fn block_hit_batches(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<Vec<BlockHit>>> + use<T> {
         let world = self.clone();
         async_stream::stream! {
             let mut interval = tokio::time::interval(interval);
             loop {
                 interval.tick().await;
-                let hits = match world.poll_block_hits().await {
-                    Ok(hits) => hits,
-                    Err(e) => match e {
-                        WorldError::Connection { source: ConnectionError::QueueFull { .. } } => {
-                            continue;
-                        }
-                        e => {
-                            yield Err(e);
-                            return;
-                        },
+                let hits = world.poll_block_hits().await;
+                match hits {
+                    Ok(hits) if hits.is_empty() => {}
+                    Ok(hits) => yield Ok(hits),
+                    Err(WorldError::Connection { source: ConnectionError::QueueFull { .. } }) => {}
+                    Err(e) => {
+                        yield Err(e);
+                        return;
                     }
-                };
-                for hit in hits {
-                    yield Ok(hit);
                 }
             }
         }
@@ -375,11 +881,42 @@ impl<T: Protocol> World<T> {
     }
 }
 
+impl<T: Protocol + Send + 'static> World<T> {
+    /// Returns a manager that keeps track of tile overrides, resetting them
+    /// once it is dropped.
+    ///
+    /// MCPI Addons server only!
+    pub fn overrides(&self) -> Overrides<T> {
+        Overrides::new(self.clone())
+    }
+
+    /// Returns a guard that changes tile light levels, restoring the original
+    /// levels once it is dropped.
+    ///
+    /// Raspberry Jam server only!
+    pub fn light_levels(&self) -> LightLevels<T> {
+        LightLevels::new(self.clone())
+    }
+}
+
+/// Who can see a chat message, and how it is shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageTarget {
+    /// Every player, prefixed with the user's name like `<username> message`.
+    #[default]
+    Everyone,
+    /// Only the local client, without any prefix.
+    Client,
+    /// Every player, without any prefix.
+    NoPrefix,
+}
+
 /// Represents a block hit event.
 ///
 /// Block hits are usually triggered when a player right clicks a block with a
 /// sword. This may differ depending on the server implementation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockHit {
     /// The coordinates of the block that was hit.
     pub location: Point3<i16>,
@@ -389,6 +926,47 @@ pub struct BlockHit {
     pub player_id: EntityId,
 }
 
+impl BlockHit {
+    /// Returns the coordinates of the block touching the face that was hit,
+    /// where a block placed against it would go, or [`None`] if they are out
+    /// of range.
+    pub fn adjacent(&self) -> Option<Point3<i16>> {
+        let normal = self.face.normal();
+        Some(Point3::new(
+            self.location.x.checked_add(normal.x)?,
+            self.location.y.checked_add(normal.y)?,
+            self.location.z.checked_add(normal.z)?,
+        ))
+    }
+
+    /// Returns the player that hit the block.
+    pub fn player<T: Protocol>(&self, world: &World<T>) -> Player<T> {
+        Player::new(world.clone(), self.player_id)
+    }
+}
+
+/// A block hit event along with the block that was hit and the player that hit
+/// it. See [`World::block_hits_with_blocks`].
+#[derive(Debug)]
+pub struct ResolvedBlockHit<T: Protocol> {
+    /// The block hit event.
+    pub hit: BlockHit,
+    /// The block at the hit's location when the hit was received.
+    pub block: Block,
+    /// The player that hit the block.
+    pub player: Player<T>,
+}
+
+impl<T: Protocol> Clone for ResolvedBlockHit<T> {
+    fn clone(&self) -> Self {
+        Self {
+            hit: self.hit,
+            block: self.block.clone(),
+            player: self.player.clone(),
+        }
+    }
+}
+
 /// Converts the floating-point position coordinates of an entity to integer
 /// tile coordinates.
 ///
@@ -409,13 +987,290 @@ pub fn pos_to_tile(pos: &Point3<f64>) -> Point3<i16> {
     )
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+
+    #[tokio::test]
+    async fn world_info() {
+        let connection = MockConnection::new(ServerKind::McpiAddons)
+            .with_response("My World")
+            .with_response("world")
+            .with_response("");
+        let world = World::new(connection);
+        assert_eq!(world.name().await.unwrap(), "My World");
+        assert_eq!(world.directory().await.unwrap(), "world");
+        assert_eq!(world.server_name().await.unwrap(), "");
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.world.name()\n",
+                "custom.world.dir()\n",
+                "custom.world.servername()\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mcpi_addons_apis_unsupported_on_vanilla() {
+        let world = World::new(MockConnection::new(ServerKind::Vanilla));
+        assert!(matches!(
+            world.name().await,
+            Err(WorldError::Unsupported {
+                server_kind: ServerKind::Vanilla
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reborn_info_is_cached() {
+        let connection = MockConnection::new(ServerKind::McpiAddons)
+            .with_response("3.0.0")
+            .with_response("true")
+            .with_response("0");
+        let world = World::new(connection);
+        for _ in 0..2 {
+            assert_eq!(
+                world.reborn_version().await.unwrap(),
+                reborn::RebornVersion::new(3, 0, 0)
+            );
+            assert!(world
+                .reborn_has_feature("Fix Sign Placement")
+                .await
+                .unwrap());
+            assert!(!world.reborn_has_feature("Disable Hunger").await.unwrap());
+        }
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.reborn.version()\n",
+                "custom.reborn.feature(Fix Sign Placement)\n",
+                "custom.reborn.feature(Disable Hunger)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_stand_at_bounds() {
+        let world = World::new(MockConnection::new(ServerKind::Vanilla));
+        assert!(!world
+            .can_stand_at(Point3::new(0, i16::MAX, 0))
+            .await
+            .unwrap());
+        assert!(!world
+            .can_stand_at(Point3::new(0, i16::MIN, 0))
+            .await
+            .unwrap());
+        assert!(world.connection().await.sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn player_names() {
+        let connection = MockConnection::new(ServerKind::McpiAddons).with_response("alice|bob");
+        let world = World::new(connection);
+        assert_eq!(world.player_names().await.unwrap(), ["alice", "bob"]);
+
+        let connection = MockConnection::new(ServerKind::RaspberryJuice)
+            .with_response("1|2")
+            .with_response("alice")
+            .with_response("bob");
+        let world = World::new(connection);
+        assert_eq!(world.player_names().await.unwrap(), ["alice", "bob"]);
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "world.getPlayerIds()\n",
+                "entity.getName(1)\n",
+                "entity.getName(2)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn player_by_name() {
+        let connection = MockConnection::new(ServerKind::RaspberryJuice)
+            .with_response("7")
+            .with_response("Fail");
+        let world = World::new(connection);
+        let player = world.player_by_name("alice").await.unwrap().unwrap();
+        assert_eq!(player.id(), EntityId(7));
+        assert!(world.player_by_name("nobody").await.unwrap().is_none());
+        assert_eq!(
+            world.connection().await.sent,
+            ["world.getPlayerId(alice)\n", "world.getPlayerId(nobody)\n"]
+        );
+    }
+
+    #[tokio::test]
+    async fn post_to_targets() {
+        let mut world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        world.post("hi\nthere").await.unwrap();
+        world.post_to("hi", MessageTarget::Client).await.unwrap();
+        world.post_to("hi", MessageTarget::NoPrefix).await.unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "chat.post(hi)\n",
+                "chat.post(there)\n",
+                "custom.post.client(hi)\n",
+                "custom.post.noPrefix(hi)\n",
+            ]
+        );
+
+        let mut world = World::new(MockConnection::new(ServerKind::Vanilla));
+        assert!(matches!(
+            world.post_to("hi", MessageTarget::Client).await,
+            Err(WorldError::Unsupported {
+                server_kind: ServerKind::Vanilla
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn block_hits_with_blocks() {
+        use futures_util::TryStreamExt;
+
+        let world = World::new(
+            MockConnection::new(ServerKind::Vanilla)
+                .with_response("")
+                .with_response("1,2,3,1,7|4,5,6,5,8")
+                .with_response("1,0")
+                .with_response("35,14"),
+        );
+        let hits = world.block_hits_with_blocks(Duration::from_millis(1));
+        let mut hits = std::pin::pin!(hits);
+        let [first, second] = [
+            hits.try_next().await.unwrap().unwrap(),
+            hits.try_next().await.unwrap().unwrap(),
+        ];
+        assert_eq!(first.hit.adjacent(), Some(Point3::new(1, 3, 3)));
+        assert_eq!(first.block, Block::from_tile(Tile::STONE));
+        assert_eq!(first.player.id(), EntityId(7));
+        assert_eq!(second.hit.adjacent(), Some(Point3::new(5, 5, 6)));
+        assert_eq!(second.block, Block::new(Tile::WOOL, TileData::RED));
+        assert_eq!(second.player.id(), EntityId(8));
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "events.block.hits()\n",
+                "events.block.hits()\n",
+                "world.getBlockWithData(1,2,3)\n",
+                "world.getBlockWithData(4,5,6)\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn block_hit_adjacent_bounds() {
+        let hit = BlockHit {
+            location: Point3::new(0, i16::MAX, 0),
+            face: BlockFace::PositiveY,
+            player_id: EntityId(1),
+        };
+        assert_eq!(hit.adjacent(), None);
+        let hit = BlockHit {
+            face: BlockFace::NegativeY,
+            ..hit
+        };
+        assert_eq!(hit.adjacent(), Some(Point3::new(0, i16::MAX - 1, 0)));
+    }
+
+    #[tokio::test]
+    async fn set_sign() {
+        let coords = Point3::new(1, 2, 3);
+        let mut world = World::new(MockConnection::new(ServerKind::RaspberryJuice));
+        world
+            .set_sign(coords, Facing::NegativeZ, &["Hello", "world"])
+            .await
+            .unwrap();
+        assert!(matches!(
+            world.set_sign(coords, Facing::NegativeZ, &["a, b"]).await,
+            Err(WorldError::InvalidSignLine { .. })
+        ));
+        assert!(matches!(
+            world.set_sign(coords, Facing::NegativeZ, &[""; 5]).await,
+            Err(WorldError::TooManySignLines { count: 5 })
+        ));
+        assert_eq!(
+            world.connection().await.sent,
+            ["world.setSign(1,2,3,68,2,Hello,world)\n"]
+        );
+
+        let mut world = World::new(MockConnection::new(ServerKind::RaspberryJam));
+        world
+            .set_sign(coords, Facing::PositiveX, &["a, b"])
+            .await
+            .unwrap();
+        assert_eq!(
+            world
+                .set_sign(coords, Facing::PositiveX, &["sixteen chars..."])
+                .await
+                .unwrap_err()
+                .to_string(),
+            r#"Invalid sign line "sixteen chars...": longer than 15 characters."#
+        );
+        assert_eq!(
+            world.connection().await.sent,
+            [concat!(
+                r#"world.setBlock(1,2,3,68,5,{"Text1":"{\"text\":\"a, b\"}","#,
+                r#""Text2":"{\"text\":\"\"}","Text3":"{\"text\":\"\"}","#,
+                r#""Text4":"{\"text\":\"\"}"})"#,
+                "\n"
+            )]
+        );
+
+        let mut world = World::new(MockConnection::new(ServerKind::Vanilla));
+        assert!(matches!(
+            world.set_sign(coords, Facing::PositiveX, &[]).await,
+            Err(WorldError::Unsupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn spawn_particles() {
+        let spec = ParticleSpec::new(particle::Particle::Flame, Point3::new(1.0, 2.0, 3.0))
+            .with_direction(nalgebra::Vector3::new(0.5, 0.0, 0.5))
+            .with_speed(0.1)
+            .with_count(2);
+
+        let mut world = World::new(MockConnection::new(ServerKind::RaspberryJam));
+        world.spawn_particles(&spec).await.unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            ["world.spawnParticle(FLAME,1,2,3,0.5,0,0.5,0.1,2)\n"]
+        );
 
-//     #[test]
-//     fn it_works() {
-//         let result = add(2, 2);
-//         assert_eq!(result, 4);
-//     }
-// }
+        let mut world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        world.spawn_particles(&spec).await.unwrap();
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.world.particle(flame,1,2,3)\n",
+                "custom.world.particle(flame,1,2,3)\n",
+            ]
+        );
+
+        for spacing in [0.0, -1.0, f64::NAN, 1e-12] {
+            assert!(matches!(
+                world
+                    .spawn_particle_trail(
+                        &spec,
+                        Point3::origin(),
+                        Point3::new(1.0, 0.0, 0.0),
+                        spacing
+                    )
+                    .await,
+                Err(WorldError::InvalidParticleSpacing { .. })
+            ));
+        }
+
+        let spec = ParticleSpec::new(particle::Particle::Heart, Point3::origin());
+        assert!(matches!(
+            world.spawn_particles(&spec).await,
+            Err(WorldError::Unsupported {
+                server_kind: ServerKind::McpiAddons
+            })
+        ));
+    }
+}
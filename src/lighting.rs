@@ -0,0 +1,136 @@
+//! Temporary changes to tile light levels, using the Raspberry Jam API
+//! extension.
+
+use std::collections::HashMap;
+
+use crate::connection::commands::raspberry_jam::BlockSetLightLevel;
+use crate::connection::{Protocol, Tile};
+use crate::util::spawn_cleanup;
+use crate::{validate_light_level, Result, World};
+
+/// Changes the light levels of tiles, remembering the level each tile had
+/// before it was first changed.
+///
+/// When the guard is dropped while levels are changed, the original levels are
+/// restored in a background task.
+///
+/// Raspberry Jam server only!
+///
+/// # Example
+///
+/// ```no_run
+/// # use mcpi::World;
+/// # use mcpi::connection::Tile;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let world = World::connect(("localhost", 4711)).await?;
+/// let mut light_levels = world.light_levels();
+/// // Make gold blocks glow until `light_levels` is dropped.
+/// light_levels.set(Tile::GOLD_BLOCK, 1.0).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LightLevels<T: Protocol + Send + 'static> {
+    world: World<T>,
+    original: HashMap<Tile, f32>,
+}
+
+impl<T: Protocol + Send + 'static> LightLevels<T> {
+    pub fn new(world: World<T>) -> Self {
+        Self {
+            world,
+            original: HashMap::new(),
+        }
+    }
+
+    /// Returns the original light level of each tile that has been changed.
+    pub const fn original(&self) -> &HashMap<Tile, f32> {
+        &self.original
+    }
+
+    /// Changes the light level of a tile. See [`World::set_tile_light_level`].
+    pub async fn set(&mut self, tile: Tile, level: f32) -> Result {
+        validate_light_level(level)?;
+        if !self.original.contains_key(&tile) {
+            let original = self.world.tile_light_level(tile).await?;
+            self.original.insert(tile, original);
+        }
+        self.world.set_tile_light_level(tile, level).await
+    }
+
+    /// Restores the original light level of every changed tile.
+    pub async fn restore(&mut self) -> Result {
+        let mut conn = self.world.connection().await;
+        for (&tile, &level) in &self.original {
+            conn.send(BlockSetLightLevel { tile, level }).await?;
+        }
+        drop(conn);
+        self.original.clear();
+        Ok(())
+    }
+}
+
+impl<T: Protocol + Send + 'static> Drop for LightLevels<T> {
+    fn drop(&mut self) {
+        if self.original.is_empty() {
+            return;
+        }
+        let original = std::mem::take(&mut self.original);
+        spawn_cleanup(&self.world, |world| async move {
+            let mut conn = world.connection().await;
+            for (tile, level) in original {
+                conn.send(BlockSetLightLevel { tile, level }).await?;
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+    use crate::WorldError;
+
+    #[tokio::test]
+    async fn light_levels_are_restored_on_drop() {
+        let world = World::new(MockConnection::new(ServerKind::RaspberryJam).with_response("0.25"));
+        let mut light_levels = world.light_levels();
+        assert!(matches!(
+            light_levels.set(Tile::STONE, f32::NAN).await,
+            Err(WorldError::InvalidLightLevel { .. })
+        ));
+        assert!(light_levels.original().is_empty());
+        light_levels.set(Tile::STONE, 1.0).await.unwrap();
+        light_levels.set(Tile::STONE, 0.5).await.unwrap();
+        assert_eq!(light_levels.original().get(&Tile::STONE), Some(&0.25));
+        assert!(matches!(
+            light_levels.set(Tile::STONE, 1.5).await,
+            Err(WorldError::InvalidLightLevel { .. })
+        ));
+        drop(light_levels);
+
+        // Let the background task run.
+        tokio::task::yield_now().await;
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "block.getLightLevel(1)\n",
+                "block.setLightLevel(1,1)\n",
+                "block.setLightLevel(1,0.5)\n",
+                "block.setLightLevel(1,0.25)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn light_levels_require_raspberry_jam() {
+        let mut world = World::new(MockConnection::new(ServerKind::RaspberryJuice));
+        assert!(matches!(
+            world.set_tile_light_level(Tile::STONE, 1.0).await,
+            Err(WorldError::Unsupported { .. })
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+//! A [`log`] logger that writes records into the game's log using the MCPI
+//! Addons API extension.
+//!
+//! Requires the `log` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use mcpi::World;
+//! # use mcpi::logger::GameLogger;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let world = World::connect(("localhost", 4711)).await?;
+//! GameLogger::new(world, log::LevelFilter::Info).await?.init()?;
+//! log::info!("Hello from a script!");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::connection::commands::mcpi_addons::{
+    CustomLogDebug, CustomLogErr, CustomLogInfo, CustomLogWarn,
+};
+use crate::connection::{ApiStr, ConnectionError, Protocol};
+use crate::{Result, World};
+
+/// How many records can be waiting to be sent before new ones are dropped.
+const QUEUE_SIZE: usize = 256;
+
+/// The maximum number of log lines that are sent to the game per time window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    pub lines: u32,
+    pub per: Duration,
+}
+
+impl Default for RateLimit {
+    /// 20 lines per second.
+    fn default() -> Self {
+        Self {
+            lines: 20,
+            per: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Forwards log records to the game's log.
+///
+/// Records are sent from a background task, so logging never waits for the
+/// server. Each line of a record is sent as its own message, and lines over
+/// the [`RateLimit`] are dropped; the number of dropped lines is reported once
+/// sending resumes.
+///
+/// MCPI Addons server only!
+#[derive(Debug)]
+pub struct GameLogger {
+    level: LevelFilter,
+    sender: mpsc::Sender<(Level, String)>,
+}
+
+impl GameLogger {
+    /// Creates a logger that forwards records at or above `level`, using the
+    /// default rate limit.
+    ///
+    /// Fails with [`WorldError::Unsupported`](crate::WorldError::Unsupported)
+    /// unless the server supports MCPI Addons.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a Tokio runtime.
+    pub async fn new<T: Protocol + Send + 'static>(
+        world: World<T>,
+        level: LevelFilter,
+    ) -> Result<Self> {
+        Self::with_rate_limit(world, level, RateLimit::default()).await
+    }
+
+    /// Creates a logger that forwards records at or above `level`.
+    ///
+    /// Fails with [`WorldError::Unsupported`](crate::WorldError::Unsupported)
+    /// unless the server supports MCPI Addons.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a Tokio runtime.
+    pub async fn with_rate_limit<T: Protocol + Send + 'static>(
+        world: World<T>,
+        level: LevelFilter,
+        rate_limit: RateLimit,
+    ) -> Result<Self> {
+        world.require_mcpi_addons().await?;
+        let (sender, receiver) = mpsc::channel(QUEUE_SIZE);
+        tokio::spawn(forward(world, receiver, rate_limit));
+        Ok(Self { level, sender })
+    }
+
+    /// Sets this logger as the global logger.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a global logger has already been set, in which case
+    /// the maximum log level is left unchanged.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+}
+
+impl Log for GameLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = format!("[{}] {}", record.target(), record.args());
+        // Records are dropped if the queue is full or the task has ended.
+        let _ = self.sender.try_send((record.level(), message));
+    }
+
+    fn flush(&self) {}
+}
+
+/// Sends queued records to the game until every logger has been dropped.
+async fn forward<T: Protocol>(
+    world: World<T>,
+    mut receiver: mpsc::Receiver<(Level, String)>,
+    rate_limit: RateLimit,
+) {
+    let mut limiter = RateLimiter::new(rate_limit);
+    while let Some((level, message)) = receiver.recv().await {
+        if let Some(dropped) = limiter.start_window(Instant::now()) {
+            let notice = format!("[mcpi] {dropped} log lines were dropped");
+            let _ = send(&world, Level::Warn, ApiStr(&notice)).await;
+        }
+        for line in lines(&message) {
+            if limiter.allow() {
+                // There is nobody to report errors to, as that would require
+                // logging them.
+                let _ = send(&world, level, line).await;
+            }
+        }
+    }
+}
+
+async fn send<T: Protocol>(
+    world: &World<T>,
+    level: Level,
+    message: ApiStr<'_>,
+) -> Result<String, ConnectionError> {
+    match level {
+        Level::Error => world.send_command(CustomLogErr { message }).await,
+        Level::Warn => world.send_command(CustomLogWarn { message }).await,
+        Level::Info => world.send_command(CustomLogInfo { message }).await,
+        Level::Debug | Level::Trace => world.send_command(CustomLogDebug { message }).await,
+    }
+}
+
+/// Splits a message into lines that can be sent to the game, skipping empty
+/// ones.
+fn lines(message: &str) -> impl Iterator<Item = ApiStr<'_>> {
+    message
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| ApiStr::new(line).expect("lines never contain line feeds"))
+}
+
+/// Counts the lines sent during fixed time windows.
+#[derive(Debug)]
+struct RateLimiter {
+    limit: RateLimit,
+    window_start: Option<Instant>,
+    sent: u32,
+    dropped: u32,
+}
+
+impl RateLimiter {
+    const fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            window_start: None,
+            sent: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Starts a new window if the current one has ended, returning the number
+    /// of lines dropped during the previous window if there were any.
+    fn start_window(&mut self, now: Instant) -> Option<u32> {
+        if self
+            .window_start
+            .is_some_and(|start| now - start < self.limit.per)
+        {
+            return None;
+        }
+        self.window_start = Some(now);
+        self.sent = 0;
+        Some(std::mem::take(&mut self.dropped)).filter(|&dropped| dropped > 0)
+    }
+
+    /// Returns whether another line can be sent during the current window.
+    const fn allow(&mut self) -> bool {
+        if self.sent < self.limit.lines {
+            self.sent += 1;
+            true
+        } else {
+            self.dropped += 1;
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+    use crate::WorldError;
+
+    #[tokio::test]
+    async fn logger_requires_mcpi_addons() {
+        let world = World::new(MockConnection::new(ServerKind::RaspberryJam));
+        assert!(matches!(
+            GameLogger::new(world, LevelFilter::Info).await,
+            Err(WorldError::Unsupported { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn logger_levels() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        for level in [
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ] {
+            send(&world, level, ApiStr("hi")).await.unwrap();
+        }
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.log.err(hi)\n",
+                "custom.log.warn(hi)\n",
+                "custom.log.info(hi)\n",
+                "custom.log.debug(hi)\n",
+                "custom.log.debug(hi)\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn logger_splits_lines() {
+        let lines = lines("first\r\n\nsecond\n").collect::<Vec<_>>();
+        assert_eq!(lines, [ApiStr("first"), ApiStr("second")]);
+    }
+
+    #[test]
+    fn logger_rate_limit() {
+        let mut limiter = RateLimiter::new(RateLimit {
+            lines: 2,
+            per: Duration::from_secs(1),
+        });
+        let start = Instant::now();
+        assert_eq!(limiter.start_window(start), None);
+        assert!(limiter.allow());
+        assert!(limiter.allow());
+        assert!(!limiter.allow());
+
+        assert_eq!(
+            limiter.start_window(start + Duration::from_millis(500)),
+            None
+        );
+        assert!(!limiter.allow());
+
+        assert_eq!(
+            limiter.start_window(start + Duration::from_secs(1)),
+            Some(2)
+        );
+        assert!(limiter.allow());
+    }
+}
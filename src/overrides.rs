@@ -0,0 +1,154 @@
+//! Bookkeeping for tile overrides, using the MCPI Addons API extension.
+
+use std::collections::HashMap;
+
+use crate::connection::commands::mcpi_addons::CustomOverride;
+use crate::connection::{Protocol, Tile};
+use crate::util::spawn_cleanup;
+use crate::{Result, World};
+
+/// Keeps track of tile overrides, which make one tile render and behave like
+/// another.
+///
+/// When the manager is dropped while overrides are active, they are removed in
+/// a background task. Only overrides made using this manager are removed.
+///
+/// MCPI Addons server only!
+///
+/// # Example
+///
+/// ```no_run
+/// # use mcpi::World;
+/// # use mcpi::connection::Tile;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let world = World::connect(("localhost", 4711)).await?;
+/// let mut overrides = world.overrides();
+/// // Make all stone look like gold until `overrides` is dropped.
+/// overrides.set(Tile::STONE, Tile::GOLD_BLOCK).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Overrides<T: Protocol + Send + 'static> {
+    world: World<T>,
+    active: HashMap<Tile, Tile>,
+}
+
+impl<T: Protocol + Send + 'static> Overrides<T> {
+    pub fn new(world: World<T>) -> Self {
+        Self {
+            world,
+            active: HashMap::new(),
+        }
+    }
+
+    /// Returns the active overrides, mapping each overridden tile to the tile
+    /// it is replaced with.
+    pub const fn active(&self) -> &HashMap<Tile, Tile> {
+        &self.active
+    }
+
+    /// Returns the tile that `before` is replaced with, if it is overridden.
+    pub fn get(&self, before: Tile) -> Option<Tile> {
+        self.active.get(&before).copied()
+    }
+
+    /// Makes the `before` tile render and behave like the `after` tile.
+    pub async fn set(&mut self, before: Tile, after: Tile) -> Result {
+        self.apply([(before, after)]).await
+    }
+
+    /// Sets multiple overrides at once. See [`Self::set`].
+    pub async fn apply(&mut self, overrides: impl IntoIterator<Item = (Tile, Tile)>) -> Result {
+        self.world.require_mcpi_addons().await?;
+        let mut conn = self.world.connection().await;
+        for (before, after) in overrides {
+            conn.send(CustomOverride { before, after }).await?;
+            if before == after {
+                self.active.remove(&before);
+            } else {
+                self.active.insert(before, after);
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops overriding the `before` tile.
+    pub async fn remove(&mut self, before: Tile) -> Result {
+        self.set(before, before).await
+    }
+
+    /// Removes every override made using this manager.
+    pub async fn reset(&mut self) -> Result {
+        let tiles = self.active.keys().copied().collect::<Vec<_>>();
+        self.apply(tiles.into_iter().map(|tile| (tile, tile))).await
+    }
+}
+
+impl<T: Protocol + Send + 'static> Drop for Overrides<T> {
+    fn drop(&mut self) {
+        if self.active.is_empty() {
+            return;
+        }
+        let tiles = std::mem::take(&mut self.active).into_keys();
+        spawn_cleanup(&self.world, |world| async move {
+            let mut conn = world.connection().await;
+            for tile in tiles {
+                conn.send(CustomOverride {
+                    before: tile,
+                    after: tile,
+                })
+                .await?;
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+
+    #[tokio::test]
+    async fn overrides_are_tracked() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        let mut overrides = world.overrides();
+        overrides
+            .apply([(Tile::STONE, Tile::GOLD_BLOCK), (Tile::DIRT, Tile::SAND)])
+            .await
+            .unwrap();
+        overrides.remove(Tile::DIRT).await.unwrap();
+        assert_eq!(overrides.get(Tile::STONE), Some(Tile::GOLD_BLOCK));
+        assert_eq!(overrides.get(Tile::DIRT), None);
+
+        overrides.reset().await.unwrap();
+        assert!(overrides.active().is_empty());
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "custom.override(1,41)\n",
+                "custom.override(3,12)\n",
+                "custom.override(3,3)\n",
+                "custom.override(1,1)\n",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn overrides_removed_on_drop() {
+        let world = World::new(MockConnection::new(ServerKind::McpiAddons));
+        let mut overrides = world.overrides();
+        overrides.set(Tile::STONE, Tile::GOLD_BLOCK).await.unwrap();
+        drop(overrides);
+
+        // Let the background task run.
+        tokio::task::yield_now().await;
+        assert_eq!(
+            world.connection().await.sent,
+            ["custom.override(1,41)\n", "custom.override(1,1)\n"]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Particle effects that work across the API extensions that support them.
+
+use std::f64::consts::TAU;
+
+use nalgebra::{distance, Point3, Vector3};
+
+use crate::connection::{MCPIExtrasParticle, RaspberryJamParticle};
+
+/// A particle effect supported by at least one API extension.
+///
+/// Use [`Particle::mcpi_addons`] and [`Particle::raspberry_jam`] to find out
+/// which servers can show a particle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Particle {
+    Bubble,
+    Crit,
+    Flame,
+    Lava,
+    Smoke,
+    LargeSmoke,
+    RedDust,
+    /// MCPI Addons only.
+    IronCrack,
+    SnowballPoof,
+    Explode,
+    /// Raspberry Jam only.
+    Cloud,
+    /// Raspberry Jam only.
+    Heart,
+    /// Raspberry Jam only.
+    Note,
+    /// Raspberry Jam only.
+    Portal,
+    /// Raspberry Jam only.
+    Splash,
+}
+
+impl Particle {
+    /// Returns the equivalent MCPI Addons particle, if there is one.
+    pub const fn mcpi_addons(self) -> Option<MCPIExtrasParticle<'static>> {
+        Some(match self {
+            Self::Bubble => MCPIExtrasParticle::BUBBLE,
+            Self::Crit => MCPIExtrasParticle::CRIT,
+            Self::Flame => MCPIExtrasParticle::FLAME,
+            Self::Lava => MCPIExtrasParticle::LAVA,
+            Self::Smoke => MCPIExtrasParticle::SMOKE,
+            Self::LargeSmoke => MCPIExtrasParticle::LARGE_SMOKE,
+            Self::RedDust => MCPIExtrasParticle::RED_DUST,
+            Self::IronCrack => MCPIExtrasParticle::IRON_CRACK,
+            Self::SnowballPoof => MCPIExtrasParticle::SNOWBALL_POOF,
+            Self::Explode => MCPIExtrasParticle::EXPLODE,
+            Self::Cloud | Self::Heart | Self::Note | Self::Portal | Self::Splash => return None,
+        })
+    }
+
+    /// Returns the equivalent Raspberry Jam particle, if there is one.
+    pub const fn raspberry_jam(self) -> Option<RaspberryJamParticle<'static>> {
+        Some(match self {
+            Self::Bubble => RaspberryJamParticle::WATER_BUBBLE,
+            Self::Crit => RaspberryJamParticle::CRIT,
+            Self::Flame => RaspberryJamParticle::FLAME,
+            Self::Lava => RaspberryJamParticle::LAVA,
+            Self::Smoke => RaspberryJamParticle::SMOKE_NORMAL,
+            Self::LargeSmoke => RaspberryJamParticle::SMOKE_LARGE,
+            Self::RedDust => RaspberryJamParticle::REDSTONE,
+            Self::SnowballPoof => RaspberryJamParticle::SNOWBALL,
+            Self::Explode => RaspberryJamParticle::EXPLOSION_NORMAL,
+            Self::Cloud => RaspberryJamParticle::CLOUD,
+            Self::Heart => RaspberryJamParticle::HEART,
+            Self::Note => RaspberryJamParticle::NOTE,
+            Self::Portal => RaspberryJamParticle::PORTAL,
+            Self::Splash => RaspberryJamParticle::WATER_SPLASH,
+            Self::IronCrack => return None,
+        })
+    }
+}
+
+/// Describes a group of particles to spawn with
+/// [`World::spawn_particles`](crate::World::spawn_particles).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleSpec {
+    pub particle: Particle,
+    /// Where the particles are spawned.
+    pub position: Point3<f64>,
+    /// How far the particles spread out along each axis.
+    ///
+    /// Only used by Raspberry Jam.
+    pub direction: Vector3<f64>,
+    /// How fast the particles move.
+    ///
+    /// Only used by Raspberry Jam.
+    pub speed: f64,
+    /// How many particles are spawned.
+    pub count: u32,
+}
+
+impl ParticleSpec {
+    /// Creates a spec for a single, stationary particle.
+    pub fn new(particle: Particle, position: Point3<f64>) -> Self {
+        Self {
+            particle,
+            position,
+            direction: Vector3::zeros(),
+            speed: 0.0,
+            count: 1,
+        }
+    }
+
+    pub const fn with_direction(mut self, direction: Vector3<f64>) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub const fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub const fn with_count(mut self, count: u32) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Returns a copy of this spec at a different position.
+    pub const fn at(mut self, position: Point3<f64>) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+/// The largest number of segments that [`line`] splits a line into.
+pub const MAX_LINE_SEGMENTS: usize = 4096;
+
+/// Returns evenly spaced points on the line from `start` to `end`, including
+/// both ends.
+///
+/// If the line would need more than [`MAX_LINE_SEGMENTS`] segments, the
+/// points are spread further apart than `spacing`.
+///
+/// # Panics
+///
+/// This function will panic if `spacing` is not positive, including if it is
+/// NaN. [`World::spawn_particle_trail`](crate::World::spawn_particle_trail)
+/// checks the spacing and returns an error instead, including when the
+/// spacing is too small to fit the line.
+pub fn line(start: Point3<f64>, end: Point3<f64>, spacing: f64) -> Vec<Point3<f64>> {
+    assert!(spacing > 0.0, "particle spacing must be positive");
+    let segments = (distance(&start, &end) / spacing)
+        .ceil()
+        .clamp(1.0, MAX_LINE_SEGMENTS as f64) as usize;
+    (0..=segments)
+        .map(|i| start + (end - start) * (i as f64 / segments as f64))
+        .collect()
+}
+
+/// Returns `points` evenly spaced points on a horizontal circle around
+/// `center`.
+pub fn ring(center: Point3<f64>, radius: f64, points: usize) -> Vec<Point3<f64>> {
+    (0..points)
+        .map(|i| {
+            let angle = TAU * i as f64 / points as f64;
+            center + Vector3::new(angle.cos(), 0.0, angle.sin()) * radius
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn particle_line_includes_ends() {
+        let points = line(Point3::origin(), Point3::new(1.0, 0.0, 0.0), 0.5);
+        assert_eq!(
+            points,
+            [
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(0.5, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn particle_line_is_capped() {
+        let points = line(Point3::origin(), Point3::new(1.0, 0.0, 0.0), 1e-12);
+        assert_eq!(points.len(), MAX_LINE_SEGMENTS + 1);
+        assert_eq!(points.last(), Some(&Point3::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn particle_ring_is_horizontal() {
+        let center = Point3::new(0.0, 5.0, 0.0);
+        let points = ring(center, 2.0, 8);
+        assert_eq!(points.len(), 8);
+        for point in points {
+            assert_eq!(point.y, 5.0);
+            assert!((distance(&point, &center) - 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn particle_support() {
+        assert_eq!(
+            Particle::Flame.mcpi_addons(),
+            Some(MCPIExtrasParticle::FLAME)
+        );
+        assert_eq!(
+            Particle::Flame.raspberry_jam(),
+            Some(RaspberryJamParticle::FLAME)
+        );
+        assert_eq!(Particle::Heart.mcpi_addons(), None);
+        assert_eq!(Particle::IronCrack.raspberry_jam(), None);
+    }
+}
@@ -0,0 +1,97 @@
+//! Information about the version of Minecraft: Pi Edition: Reborn the game is
+//! running, using the MCPI Addons API extension.
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use snafu::{ResultExt, Snafu};
+
+/// A Minecraft: Pi Edition: Reborn version number, such as `3.0.0`.
+///
+/// Missing minor and patch numbers are treated as zero, and anything after a
+/// `-` or `+` (such as a pre-release tag) is ignored when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RebornVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RebornVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for RebornVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for RebornVersion {
+    type Err = ParseRebornVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let core = s
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default();
+        let mut parts = core.split('.');
+        let mut next = || {
+            parts
+                .next()
+                .map_or(Ok(0), str::parse)
+                .context(ParseRebornVersionSnafu { version: s })
+        };
+        let version = Self::new(next()?, next()?, next()?);
+        if parts.next().is_some() {
+            return TooManyPartsSnafu { version: s }.fail();
+        }
+        Ok(version)
+    }
+}
+
+/// An error that occurs when a [`RebornVersion`] cannot be parsed.
+#[derive(Debug, Snafu)]
+pub enum ParseRebornVersionError {
+    #[snafu(display("Invalid Reborn version `{version}`: {source}"))]
+    ParseRebornVersion {
+        version: String,
+        source: ParseIntError,
+    },
+    #[snafu(display("Invalid Reborn version `{version}`: too many parts"))]
+    TooManyParts { version: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reborn_version_parse() {
+        assert_eq!(
+            "3.0.0".parse::<RebornVersion>().unwrap(),
+            RebornVersion::new(3, 0, 0)
+        );
+        assert_eq!(
+            "v2.5-dev".parse::<RebornVersion>().unwrap(),
+            RebornVersion::new(2, 5, 0)
+        );
+        assert!("1.2.3.4".parse::<RebornVersion>().is_err());
+        assert!("unknown".parse::<RebornVersion>().is_err());
+    }
+
+    #[test]
+    fn reborn_version_ordering() {
+        assert!(RebornVersion::new(2, 10, 0) > RebornVersion::new(2, 9, 1));
+        assert_eq!(RebornVersion::new(2, 10, 0).to_string(), "2.10.0");
+    }
+}
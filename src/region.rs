@@ -0,0 +1,166 @@
+//! A cuboid grid of blocks that can be transformed and placed in the world.
+
+use nalgebra::{Point3, Vector3};
+
+use crate::block::transform::Axis;
+use crate::Block;
+
+/// A cuboid of blocks, such as a saved structure.
+///
+/// Positions in a region are relative to its minimum corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    size: Vector3<usize>,
+    blocks: Vec<Block>,
+}
+
+impl Region {
+    /// Creates a region of the given size, filled with a single block.
+    pub fn new(size: Vector3<usize>, fill: &Block) -> Self {
+        Self::from_fn(size, |_| fill.clone())
+    }
+
+    /// Creates a region of the given size, calling `f` with each position to
+    /// get the block there.
+    pub fn from_fn(size: Vector3<usize>, mut f: impl FnMut(Point3<usize>) -> Block) -> Self {
+        let mut blocks = Vec::with_capacity(size.x * size.y * size.z);
+        for y in 0..size.y {
+            for z in 0..size.z {
+                for x in 0..size.x {
+                    blocks.push(f(Point3::new(x, y, z)));
+                }
+            }
+        }
+        Self { size, blocks }
+    }
+
+    /// Returns the number of blocks along each axis.
+    pub const fn size(&self) -> Vector3<usize> {
+        self.size
+    }
+
+    fn index(&self, position: Point3<usize>) -> Option<usize> {
+        let inside =
+            position.x < self.size.x && position.y < self.size.y && position.z < self.size.z;
+        inside.then(|| position.x + self.size.x * (position.z + self.size.z * position.y))
+    }
+
+    /// Returns the block at the given position, or [`None`] if it is outside of
+    /// the region.
+    pub fn get(&self, position: Point3<usize>) -> Option<&Block> {
+        self.index(position).map(|i| &self.blocks[i])
+    }
+
+    /// Returns a mutable reference to the block at the given position, or
+    /// [`None`] if it is outside of the region.
+    pub fn get_mut(&mut self, position: Point3<usize>) -> Option<&mut Block> {
+        self.index(position).map(|i| &mut self.blocks[i])
+    }
+
+    /// Iterates over each position in the region and the block there.
+    pub fn iter(&self) -> impl Iterator<Item = (Point3<usize>, &Block)> + '_ {
+        let size = self.size;
+        self.blocks.iter().enumerate().map(move |(i, block)| {
+            let x = i % size.x;
+            let z = i / size.x % size.z;
+            let y = i / (size.x * size.z);
+            (Point3::new(x, y, z), block)
+        })
+    }
+
+    /// Rotates the region and each of its blocks clockwise (when seen from
+    /// above) by the given number of 90° turns.
+    ///
+    /// Quarter turns swap the region's X and Z sizes.
+    #[must_use]
+    pub fn rotate_y(&self, quarter_turns: i32) -> Self {
+        let turns = quarter_turns.rem_euclid(4);
+        let size = if turns % 2 == 0 {
+            self.size
+        } else {
+            Vector3::new(self.size.z, self.size.y, self.size.x)
+        };
+        Self::from_fn(size, |p| {
+            // Find the position that ends up at `p` by turning it back.
+            let (x, z) = match turns {
+                0 => (p.x, p.z),
+                1 => (p.z, size.x - 1 - p.x),
+                2 => (size.x - 1 - p.x, size.z - 1 - p.z),
+                _ => (size.z - 1 - p.z, p.x),
+            };
+            self.get(Point3::new(x, p.y, z))
+                .expect("rotated position is inside the region")
+                .rotate_y(quarter_turns)
+        })
+    }
+
+    /// Mirrors the region and each of its blocks along the given axis.
+    #[must_use]
+    pub fn mirror(&self, axis: Axis) -> Self {
+        Self::from_fn(self.size, |p| {
+            let source = match axis {
+                Axis::X => Point3::new(self.size.x - 1 - p.x, p.y, p.z),
+                Axis::Z => Point3::new(p.x, p.y, self.size.z - 1 - p.z),
+            };
+            self.get(source)
+                .expect("mirrored position is inside the region")
+                .mirror(axis)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::{Tile, TileData};
+
+    /// A 2x1x3 region with a stone block at the origin and stairs facing +X.
+    fn region() -> Region {
+        Region::from_fn(Vector3::new(2, 1, 3), |p| {
+            if p == Point3::origin() {
+                Block::from_tile(Tile::STONE)
+            } else {
+                Block::new(Tile::WOODEN_STAIRS, TileData::STAIRS_X_POSITIVE)
+            }
+        })
+    }
+
+    #[test]
+    fn region_rotate_y() {
+        let rotated = region().rotate_y(1);
+        assert_eq!(rotated.size(), Vector3::new(3, 1, 2));
+        // Turning clockwise moves the origin corner to the +X side.
+        assert_eq!(
+            rotated.get(Point3::new(2, 0, 0)),
+            Some(&Block::from_tile(Tile::STONE))
+        );
+        assert_eq!(
+            rotated.get(Point3::new(0, 0, 0)).unwrap().data,
+            TileData::STAIRS_Z_POSITIVE
+        );
+        assert_eq!(region().rotate_y(4), region());
+        assert_eq!(region().rotate_y(1).rotate_y(-1), region());
+    }
+
+    #[test]
+    fn region_mirror() {
+        let mirrored = region().mirror(Axis::X);
+        assert_eq!(
+            mirrored.get(Point3::new(1, 0, 0)),
+            Some(&Block::from_tile(Tile::STONE))
+        );
+        assert_eq!(
+            mirrored.get(Point3::new(0, 0, 0)).unwrap().data,
+            TileData::STAIRS_X_NEGATIVE
+        );
+        assert_eq!(mirrored.mirror(Axis::X), region());
+    }
+
+    #[test]
+    fn region_iter() {
+        let positions = region().iter().map(|(p, _)| p).collect::<Vec<_>>();
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[1], Point3::new(1, 0, 0));
+        assert_eq!(positions[2], Point3::new(0, 0, 1));
+    }
+}
@@ -0,0 +1,549 @@
+//! Region triggers that run callbacks when players walk into, stay inside, or
+//! walk out of an area of the world.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Formatter};
+use std::pin::pin;
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+
+use nalgebra::{distance, Point3, Vector3};
+
+use crate::connection::{ConnectionError, EntityId, Protocol};
+use crate::entity::{Entity, MovementSample, MovementTracker, Player};
+use crate::{Result, World, WorldError};
+
+/// An area of the world that can be watched by a [`Trigger`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerArea {
+    /// An axis-aligned box, inclusive of its bounds.
+    Cuboid { min: Point3<f64>, max: Point3<f64> },
+    /// A sphere, inclusive of its surface.
+    Sphere { center: Point3<f64>, radius: f64 },
+}
+
+impl TriggerArea {
+    /// Creates an axis-aligned box from any two of its opposite corners.
+    pub fn cuboid(corner_1: Point3<f64>, corner_2: Point3<f64>) -> Self {
+        Self::Cuboid {
+            min: corner_1.inf(&corner_2),
+            max: corner_1.sup(&corner_2),
+        }
+    }
+
+    /// Creates an axis-aligned box containing every tile inclusively contained
+    /// in the given cuboid.
+    pub fn tiles(corner_1: Point3<i16>, corner_2: Point3<i16>) -> Self {
+        let min = corner_1.inf(&corner_2);
+        let max = corner_1.sup(&corner_2);
+        Self::Cuboid {
+            min: min.cast(),
+            max: max.cast() + Vector3::repeat(1.0),
+        }
+    }
+
+    /// Creates a sphere with the given center and radius.
+    pub const fn sphere(center: Point3<f64>, radius: f64) -> Self {
+        Self::Sphere { center, radius }
+    }
+
+    /// Returns whether the given position is inside of the area.
+    pub fn contains(&self, position: &Point3<f64>) -> bool {
+        match self {
+            Self::Cuboid { min, max } => {
+                position.iter().zip(min.iter()).all(|(p, min)| p >= min)
+                    && position.iter().zip(max.iter()).all(|(p, max)| p <= max)
+            }
+            Self::Sphere { center, radius } => distance(center, position) <= *radius,
+        }
+    }
+}
+
+/// The kind of event that caused a [`Trigger`] callback to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerEvent {
+    /// The entity walked into the trigger's area.
+    Enter,
+    /// The entity was inside of the trigger's area when it was polled, and was
+    /// also inside during the previous poll.
+    Stay,
+    /// The entity walked out of the trigger's area, or left the game while
+    /// inside of it.
+    Exit,
+}
+
+type TriggerCallback = Box<dyn FnMut(EntityId) + Send>;
+
+/// Runs callbacks when entities enter, stay inside, or exit an area.
+///
+/// Triggers do nothing on their own; they must be added to a
+/// [`TriggerRegistry`], which samples the movement of players.
+///
+/// # Example
+///
+/// ```
+/// # use mcpi::trigger::{Trigger, TriggerArea, TriggerRegistry};
+/// # use nalgebra::Point3;
+/// # use std::time::Duration;
+/// let mut registry = TriggerRegistry::new();
+/// registry.add(
+///     Trigger::new(TriggerArea::sphere(Point3::new(0.0, 0.0, 0.0), 5.0))
+///         .on_enter(|id| println!("Player #{id} found the treasure!"))
+///         .with_cooldown(Duration::from_secs(30))
+///         .with_poll_interval(Duration::from_secs(1)),
+/// );
+/// ```
+pub struct Trigger {
+    area: TriggerArea,
+    cooldown: Duration,
+    poll_interval: Duration,
+    last_polled: Option<Instant>,
+    on_enter: Option<TriggerCallback>,
+    on_stay: Option<TriggerCallback>,
+    on_exit: Option<TriggerCallback>,
+    inside: HashSet<EntityId>,
+    last_fired: HashMap<(EntityId, TriggerEvent), Instant>,
+}
+
+impl Debug for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trigger")
+            .field("area", &self.area)
+            .field("cooldown", &self.cooldown)
+            .field("poll_interval", &self.poll_interval)
+            .field("inside", &self.inside)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Trigger {
+    /// Creates a trigger that watches the given area and has no callbacks.
+    pub fn new(area: TriggerArea) -> Self {
+        Self {
+            area,
+            cooldown: Duration::ZERO,
+            poll_interval: Duration::ZERO,
+            last_polled: None,
+            on_enter: None,
+            on_stay: None,
+            on_exit: None,
+            inside: HashSet::new(),
+            last_fired: HashMap::new(),
+        }
+    }
+
+    /// Sets the callback that runs when an entity walks into the area.
+    pub fn on_enter(mut self, callback: impl FnMut(EntityId) + Send + 'static) -> Self {
+        self.on_enter = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback that runs each poll while an entity remains inside of
+    /// the area.
+    pub fn on_stay(mut self, callback: impl FnMut(EntityId) + Send + 'static) -> Self {
+        self.on_stay = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback that runs when an entity walks out of the area.
+    pub fn on_exit(mut self, callback: impl FnMut(EntityId) + Send + 'static) -> Self {
+        self.on_exit = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the minimum amount of time between two callbacks of the same kind
+    /// for the same entity.
+    ///
+    /// Callbacks that would run during the cooldown are skipped, but the
+    /// trigger still keeps track of which entities are inside of its area.
+    pub const fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Sets the minimum amount of time between two checks of the trigger's
+    /// area.
+    ///
+    /// Samples that arrive sooner are ignored, which lets cheap triggers run
+    /// at the registry's full rate while others are checked less often.
+    pub const fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Returns the area watched by this trigger.
+    pub const fn area(&self) -> &TriggerArea {
+        &self.area
+    }
+
+    /// Returns whether the given entity was inside of the area during the last
+    /// poll.
+    pub fn is_inside(&self, entity: EntityId) -> bool {
+        self.inside.contains(&entity)
+    }
+
+    fn fire(&mut self, event: TriggerEvent, entity: EntityId, now: Instant) {
+        let callback = match event {
+            TriggerEvent::Enter => &mut self.on_enter,
+            TriggerEvent::Stay => &mut self.on_stay,
+            TriggerEvent::Exit => &mut self.on_exit,
+        };
+        let Some(callback) = callback else {
+            return;
+        };
+
+        if let Some(last_fired) = self.last_fired.get(&(entity, event)) {
+            if now.saturating_duration_since(*last_fired) < self.cooldown {
+                return;
+            }
+        }
+        self.last_fired.insert((entity, event), now);
+        callback(entity);
+    }
+
+    fn update(&mut self, samples: &[(EntityId, MovementSample)], now: Instant) {
+        if let Some(last_polled) = self.last_polled {
+            if now.saturating_duration_since(last_polled) < self.poll_interval {
+                return;
+            }
+        }
+        self.last_polled = Some(now);
+
+        let mut inside = HashSet::new();
+        for (entity, sample) in samples {
+            if self.area.contains(&sample.position) {
+                inside.insert(*entity);
+            }
+        }
+
+        let exited = self.inside.difference(&inside).copied().collect::<Vec<_>>();
+        for entity in exited {
+            self.fire(TriggerEvent::Exit, entity, now);
+        }
+        for (entity, _) in samples {
+            if !inside.contains(entity) {
+                continue;
+            }
+            let event = if self.inside.contains(entity) {
+                TriggerEvent::Stay
+            } else {
+                TriggerEvent::Enter
+            };
+            self.fire(event, *entity, now);
+        }
+
+        self.inside = inside;
+
+        // Forget entities once their cooldowns expire or they leave the game,
+        // so that long-running triggers do not grow without bound.
+        let present = samples
+            .iter()
+            .map(|(entity, _)| *entity)
+            .collect::<HashSet<_>>();
+        let cooldown = self.cooldown;
+        self.last_fired.retain(|(entity, _), last_fired| {
+            present.contains(entity) && now.saturating_duration_since(*last_fired) < cooldown
+        });
+    }
+}
+
+/// A handle to a [`Trigger`] that has been added to a [`TriggerRegistry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TriggerId(u64);
+
+/// A collection of [`Trigger`]s that are checked against the movement of
+/// players in the world.
+#[derive(Debug, Default)]
+pub struct TriggerRegistry {
+    triggers: Vec<(TriggerId, Trigger)>,
+    trackers: HashMap<EntityId, MovementTracker>,
+    next_id: u64,
+}
+
+impl TriggerRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            triggers: Vec::new(),
+            trackers: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a trigger to the registry, returning a handle that can be used to
+    /// remove it.
+    pub fn add(&mut self, trigger: Trigger) -> TriggerId {
+        let id = TriggerId(self.next_id);
+        self.next_id += 1;
+        self.triggers.push((id, trigger));
+        id
+    }
+
+    /// Removes a trigger from the registry, returning it if it existed.
+    pub fn remove(&mut self, id: TriggerId) -> Option<Trigger> {
+        let idx = self.triggers.iter().position(|(other, _)| *other == id)?;
+        Some(self.triggers.remove(idx).1)
+    }
+
+    /// Returns the trigger with the given handle.
+    pub fn get(&self, id: TriggerId) -> Option<&Trigger> {
+        self.triggers
+            .iter()
+            .find(|(other, _)| *other == id)
+            .map(|(_, trigger)| trigger)
+    }
+
+    /// Runs the callbacks of every trigger that is due using the given
+    /// movement samples.
+    ///
+    /// Entities that were previously inside of a trigger's area but are not
+    /// included in `samples` are treated as having exited it.
+    pub fn update(&mut self, samples: &[(EntityId, MovementSample)], now: Instant) {
+        for (_, trigger) in &mut self.triggers {
+            trigger.update(samples, now);
+        }
+    }
+
+    /// Samples the movement of all players in the world and runs the callbacks
+    /// of every trigger that is due.
+    ///
+    /// Players whose position cannot be fetched, such as players that have
+    /// just left the game, are treated as having left.
+    pub async fn poll<T: Protocol>(&mut self, world: &World<T>) -> Result {
+        let players = world.all_players().await?;
+        let now = Instant::now();
+        let mut samples = Vec::with_capacity(players.len());
+        for player in players {
+            let position = match player.get_position().await {
+                Ok(position) => position,
+                Err(e @ WorldError::Connection { .. }) => return Err(e),
+                Err(_) => continue,
+            };
+            let tracker = self.trackers.entry(player.id()).or_default();
+            if let Some(sample) = tracker.update(position, now) {
+                samples.push((player.id(), sample));
+            }
+        }
+        self.trackers
+            .retain(|entity, _| samples.iter().any(|(other, _)| other == entity));
+        self.update(&samples, now);
+        Ok(())
+    }
+
+    /// Polls the world for player movement at the given interval, running the
+    /// callbacks of every trigger that is due, until an error occurs. If the
+    /// connection's event queue is full, polls will not be sent.
+    ///
+    /// The interval should be no longer than the shortest poll interval of the
+    /// registry's triggers.
+    pub async fn run<T: Protocol>(&mut self, world: &World<T>, interval: Duration) -> Result {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            let result = self.poll(world).await;
+            match result {
+                Ok(()) => {}
+                Err(WorldError::Connection {
+                    source: ConnectionError::QueueFull { .. },
+                }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Runs the callbacks of every trigger that is due as a single player
+    /// moves, using the player's [`Entity::movement`] stream, until an error
+    /// occurs.
+    pub async fn watch<T: Protocol>(&mut self, player: &Player<T>, interval: Duration) -> Result {
+        let mut samples = pin!(player.movement(interval));
+        loop {
+            let sample = std::future::poll_fn(|cx| samples.as_mut().poll_next(cx)).await;
+            let Some(sample) = sample else {
+                return Ok(());
+            };
+            self.update(&[(player.id(), sample?)], Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::ServerKind;
+
+    type EventLog = Arc<Mutex<Vec<(TriggerEvent, EntityId)>>>;
+
+    fn recording_trigger(area: TriggerArea) -> (Trigger, EventLog) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (enter, stay, exit) = (events.clone(), events.clone(), events.clone());
+        let trigger = Trigger::new(area)
+            .on_enter(move |id| enter.lock().unwrap().push((TriggerEvent::Enter, id)))
+            .on_stay(move |id| stay.lock().unwrap().push((TriggerEvent::Stay, id)))
+            .on_exit(move |id| exit.lock().unwrap().push((TriggerEvent::Exit, id)));
+        (trigger, events)
+    }
+
+    fn at(entity: EntityId, position: Point3<f64>) -> (EntityId, MovementSample) {
+        let sample = MovementTracker::new().update(position, Instant::now());
+        (entity, sample.unwrap())
+    }
+
+    #[test]
+    fn trigger_area_contains() {
+        let cuboid = TriggerArea::cuboid(Point3::new(2.0, 2.0, 2.0), Point3::new(0.0, 0.0, 0.0));
+        assert!(cuboid.contains(&Point3::new(1.0, 2.0, 0.0)));
+        assert!(!cuboid.contains(&Point3::new(1.0, 2.5, 0.0)));
+
+        let tiles = TriggerArea::tiles(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+        assert!(tiles.contains(&Point3::new(1.9, 1.9, 1.9)));
+
+        let sphere = TriggerArea::sphere(Point3::new(0.0, 0.0, 0.0), 1.0);
+        assert!(sphere.contains(&Point3::new(0.0, 1.0, 0.0)));
+        assert!(!sphere.contains(&Point3::new(0.8, 0.8, 0.0)));
+    }
+
+    #[test]
+    fn trigger_fires_enter_stay_exit() {
+        let (trigger, events) = recording_trigger(TriggerArea::sphere(Point3::origin(), 1.0));
+        let mut registry = TriggerRegistry::new();
+        registry.add(trigger);
+
+        let player = EntityId(1);
+        let now = Instant::now();
+        registry.update(&[at(player, Point3::new(5.0, 0.0, 0.0))], now);
+        registry.update(&[at(player, Point3::new(0.5, 0.0, 0.0))], now);
+        registry.update(&[at(player, Point3::new(0.0, 0.0, 0.0))], now);
+        registry.update(&[at(player, Point3::new(5.0, 0.0, 0.0))], now);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (TriggerEvent::Enter, player),
+                (TriggerEvent::Stay, player),
+                (TriggerEvent::Exit, player),
+            ]
+        );
+    }
+
+    #[test]
+    fn trigger_exits_when_entity_leaves_game() {
+        let (trigger, events) = recording_trigger(TriggerArea::sphere(Point3::origin(), 1.0));
+        let mut registry = TriggerRegistry::new();
+        let id = registry.add(trigger);
+
+        let player = EntityId(1);
+        let now = Instant::now();
+        registry.update(&[at(player, Point3::origin())], now);
+        assert!(registry.get(id).unwrap().is_inside(player));
+        registry.update(&[], now);
+        assert!(!registry.get(id).unwrap().is_inside(player));
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [(TriggerEvent::Enter, player), (TriggerEvent::Exit, player)]
+        );
+    }
+
+    #[test]
+    fn trigger_cooldown_skips_callbacks() {
+        let (trigger, events) = recording_trigger(TriggerArea::sphere(Point3::origin(), 1.0));
+        let mut registry = TriggerRegistry::new();
+        registry.add(trigger.with_cooldown(Duration::from_secs(10)));
+
+        let player = EntityId(1);
+        let inside = [at(player, Point3::origin())];
+        let outside = [at(player, Point3::new(5.0, 0.0, 0.0))];
+        let start = Instant::now();
+        registry.update(&inside, start);
+        registry.update(&outside, start + Duration::from_secs(1));
+        registry.update(&inside, start + Duration::from_secs(2));
+        registry.update(&outside, start + Duration::from_secs(11));
+        registry.update(&inside, start + Duration::from_secs(12));
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (TriggerEvent::Enter, player),
+                (TriggerEvent::Exit, player),
+                (TriggerEvent::Exit, player),
+                (TriggerEvent::Enter, player),
+            ]
+        );
+    }
+
+    #[test]
+    fn trigger_poll_interval_skips_samples() {
+        let (trigger, events) = recording_trigger(TriggerArea::sphere(Point3::origin(), 1.0));
+        let mut registry = TriggerRegistry::new();
+        registry.add(trigger.with_poll_interval(Duration::from_secs(5)));
+
+        let player = EntityId(1);
+        let start = Instant::now();
+        registry.update(&[at(player, Point3::new(5.0, 0.0, 0.0))], start);
+        registry.update(
+            &[at(player, Point3::origin())],
+            start + Duration::from_secs(1),
+        );
+        assert!(events.lock().unwrap().is_empty());
+        registry.update(
+            &[at(player, Point3::origin())],
+            start + Duration::from_secs(5),
+        );
+        assert_eq!(*events.lock().unwrap(), [(TriggerEvent::Enter, player)]);
+    }
+
+    #[test]
+    fn trigger_forgets_expired_cooldowns() {
+        let trigger = Trigger::new(TriggerArea::sphere(Point3::origin(), 1.0)).on_enter(|_| {});
+        let mut registry = TriggerRegistry::new();
+        let id = registry.add(trigger.with_cooldown(Duration::from_secs(10)));
+
+        let (player_1, player_2) = (EntityId(1), EntityId(2));
+        let start = Instant::now();
+        registry.update(
+            &[
+                at(player_1, Point3::origin()),
+                at(player_2, Point3::origin()),
+            ],
+            start,
+        );
+        assert_eq!(registry.get(id).unwrap().last_fired.len(), 2);
+        registry.update(&[at(player_1, Point3::origin())], start);
+        assert_eq!(registry.get(id).unwrap().last_fired.len(), 1);
+        registry.update(
+            &[at(player_1, Point3::origin())],
+            start + Duration::from_secs(10),
+        );
+        assert!(registry.get(id).unwrap().last_fired.is_empty());
+    }
+
+    #[tokio::test]
+    async fn trigger_poll_skips_missing_players() {
+        let connection = MockConnection::new(ServerKind::Vanilla)
+            .with_response("1|2")
+            .with_response("0.5,0,0")
+            .with_response("Fail");
+        let world = World::new(connection);
+        let (trigger, events) = recording_trigger(TriggerArea::sphere(Point3::origin(), 1.0));
+        let mut registry = TriggerRegistry::new();
+        registry.add(trigger);
+
+        registry.poll(&world).await.unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            [(TriggerEvent::Enter, EntityId(1))]
+        );
+        assert_eq!(
+            world.connection().await.sent,
+            [
+                "world.getPlayerIds()\n",
+                "entity.getPos(1)\n",
+                "entity.getPos(2)\n",
+            ]
+        );
+    }
+}
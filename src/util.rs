@@ -1,12 +1,14 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{Display, Write};
+use std::future::Future;
 use std::sync::LazyLock;
 
 use derive_more::derive::AsRef;
 use nalgebra::{Point, Scalar};
 
-use crate::{Result, WorldError};
+use crate::connection::Protocol;
+use crate::{Result, World, WorldError};
 
 pub fn parse_point<T, E, const D: usize>(s: &str) -> Result<nalgebra::Point<T, D>>
 where
@@ -20,6 +22,26 @@ where
     Ok(Point::<T, D>::from_slice(&parts))
 }
 
+/// Spawns a task that cleans up after a value that has been dropped, such as
+/// by releasing keys or undoing changes to the world.
+///
+/// Nothing is cleaned up if there is no Tokio runtime running. Errors are
+/// ignored, since there is nobody left to report them to.
+pub(crate) fn spawn_cleanup<T, F, Fut>(world: &World<T>, cleanup: F)
+where
+    T: Protocol + Send + 'static,
+    F: FnOnce(World<T>) -> Fut,
+    Fut: Future<Output = Result> + Send + 'static,
+{
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    let cleanup = cleanup(world.clone());
+    runtime.spawn(async move {
+        let _ = cleanup.await;
+    });
+}
+
 // Port of Minecraft Pi: Reborn's character handling to Rust
 
 const CP437_CHARACTERS: usize = 256;
@@ -98,6 +120,31 @@ impl From<Vec<u8>> for Cp437String<'static> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::connection::commands::ChatPost;
+    use crate::connection::testing::MockConnection;
+    use crate::connection::{ChatString, ServerKind};
+
+    #[tokio::test]
+    async fn cleanup_runs_in_background() {
+        let world = World::new(MockConnection::new(ServerKind::Vanilla));
+        spawn_cleanup(&world, |world| async move {
+            let message = ChatString::from_str_lossy("Goodbye");
+            world.send_command(ChatPost { message }).await?;
+            Ok(())
+        });
+
+        // Let the background task run.
+        tokio::task::yield_now().await;
+        assert_eq!(world.connection().await.sent, ["chat.post(Goodbye)\n"]);
+    }
+
+    #[test]
+    fn cleanup_needs_runtime() {
+        let world = World::new(MockConnection::new(ServerKind::Vanilla));
+        spawn_cleanup(&world, |_| -> std::future::Ready<Result> {
+            unreachable!("cleanup ran without a runtime")
+        });
+    }
 
     #[test]
     fn test_cp437_to_string() {